@@ -0,0 +1,37 @@
+-- ============================================
+-- Migration: Create address_labels table
+-- Date: 2025-11-01
+-- Description: Human-readable labels for well-known addresses
+--              (exchange hot wallets, routers, protocol contracts)
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS address_labels (
+    id SERIAL PRIMARY KEY,
+    address TEXT NOT NULL,
+    chainid BIGINT NOT NULL,
+    label TEXT NOT NULL,
+    label_source TEXT NOT NULL DEFAULT 'manual',
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- One label per address per chain, regardless of address casing
+CREATE UNIQUE INDEX IF NOT EXISTS idx_address_labels_address_chainid
+ON address_labels(LOWER(address), chainid);
+
+COMMENT ON TABLE address_labels IS 'Labels for well-known addresses, used to enrich query responses';
+COMMENT ON COLUMN address_labels.label_source IS 'Origin of the label: manual (manager RPC) or seed (migration)';
+
+-- Initial curated labels (Ethereum mainnet)
+INSERT INTO address_labels (address, chainid, label, label_source) VALUES
+    ('0x0000000000000000000000000000000000000000', 1, 'Null Address', 'seed'),
+    ('0x28c6c06298d514db089934071355e5743bf21d60', 1, 'Binance 14', 'seed'),
+    ('0xbe0eb53f46cd790cd13851d5eff43d12404d33e8', 1, 'Binance 7', 'seed'),
+    ('0xa9d1e08c7793af67e9d92fe308d5697fb81d3e43', 1, 'Coinbase 10', 'seed'),
+    ('0xda9dfa130df4de4673b89022ee50ff26f6ea73cf', 1, 'Kraken 13', 'seed'),
+    ('0x6cc5f688a315f3dc28a7781717a9a798a59fda7b', 1, 'OKX 7', 'seed'),
+    ('0xc02aaa39b223fe8d0a0e5c4f27ead9083c756cc2', 1, 'Wrapped Ether (WETH)', 'seed'),
+    ('0x7a250d5630b4cf539739df2c5dacb4c659f2488d', 1, 'Uniswap V2: Router 2', 'seed'),
+    ('0xe592427a0aece92de3edee1f18e0157c05861564', 1, 'Uniswap V3: Router', 'seed'),
+    ('0x3fc91a3afd70395cd496c647d5a6cc9d4b2b7fad', 1, 'Uniswap: Universal Router', 'seed'),
+    ('0x1111111254eeb25477b68fb85ed929f73a960582', 1, '1inch v5: Aggregation Router', 'seed')
+ON CONFLICT DO NOTHING;
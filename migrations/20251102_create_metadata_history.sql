@@ -0,0 +1,24 @@
+-- ============================================
+-- Migration: Create metadata_history table
+-- Date: 2025-11-02
+-- Description: Audit trail of symbol/name changes detected when
+--              metadata is force-refreshed (rebrands, scam renames)
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS metadata_history (
+    id SERIAL PRIMARY KEY,
+    metadata_id INTEGER NOT NULL REFERENCES metadata(id) ON DELETE CASCADE,
+    chainid BIGINT NOT NULL,
+    address TEXT NOT NULL,
+    field TEXT NOT NULL,
+    old_value TEXT,
+    new_value TEXT,
+    changed_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- History lookups are by token (chainid + address), newest first
+CREATE INDEX IF NOT EXISTS idx_metadata_history_token
+ON metadata_history(chainid, address, changed_at DESC);
+
+COMMENT ON TABLE metadata_history IS 'Changes to metadata fields recorded during force updates';
+COMMENT ON COLUMN metadata_history.field IS 'Name of the changed metadata column (symbol, name)';
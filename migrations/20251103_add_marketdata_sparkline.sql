@@ -0,0 +1,10 @@
+-- ============================================
+-- Migration: Add sparkline_7d to marketdata
+-- Date: 2025-11-03
+-- Description: Stores CoinGecko's 7-day hourly price series
+--              (only populated when FETCH_SPARKLINE=true)
+-- ============================================
+
+ALTER TABLE marketdata ADD COLUMN IF NOT EXISTS sparkline_7d JSONB;
+
+COMMENT ON COLUMN marketdata.sparkline_7d IS 'JSON array of ~168 hourly USD prices over the last 7 days';
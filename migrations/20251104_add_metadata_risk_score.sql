@@ -0,0 +1,10 @@
+-- ============================================
+-- Migration: Add risk_score to metadata
+-- Date: 2025-11-04
+-- Description: Numeric risk score aggregated from verification status,
+--              scam flag, metadata completeness and market cap
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS risk_score SMALLINT;
+
+COMMENT ON COLUMN metadata.risk_score IS 'Risk score 0-100; risk_level holds its bucket (low/medium/high)';
@@ -0,0 +1,10 @@
+-- ============================================
+-- Migration: Add social_links to metadata
+-- Date: 2025-11-05
+-- Description: Social/contact links from the CoinGecko coin detail
+--              response (twitter, telegram, github, chat, announcements)
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS social_links JSONB;
+
+COMMENT ON COLUMN metadata.social_links IS 'JSON object: twitter, telegram, github, chat, announcement';
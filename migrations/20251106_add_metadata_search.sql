@@ -0,0 +1,22 @@
+-- ============================================
+-- Migration: Add full-text search to metadata
+-- Date: 2025-11-06
+-- Description: Weighted tsvector over symbol/name/description with a
+--              GIN index, plus trigram indexes for short-query ILIKE
+-- ============================================
+
+-- 'simple' config: token names/symbols are not natural language, so no stemming
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS search_vector tsvector
+GENERATED ALWAYS AS (
+    setweight(to_tsvector('simple', coalesce(symbol, '')), 'A') ||
+    setweight(to_tsvector('simple', coalesce(name, '')), 'B') ||
+    setweight(to_tsvector('simple', coalesce(description, '')), 'C')
+) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_metadata_search_vector ON metadata USING GIN (search_vector);
+
+-- Trigram indexes back the ILIKE fallback used for 1-2 character queries
+CREATE EXTENSION IF NOT EXISTS pg_trgm;
+
+CREATE INDEX IF NOT EXISTS idx_metadata_symbol_trgm ON metadata USING GIN (symbol gin_trgm_ops);
+CREATE INDEX IF NOT EXISTS idx_metadata_name_trgm ON metadata USING GIN (name gin_trgm_ops);
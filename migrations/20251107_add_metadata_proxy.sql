@@ -0,0 +1,10 @@
+-- ============================================
+-- Migration: Add proxy columns to metadata
+-- Date: 2025-11-07
+-- Description: Proxy implementation address reported by Blockscout
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS proxy_implementation TEXT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS is_proxy BOOLEAN;
+
+COMMENT ON COLUMN metadata.proxy_implementation IS 'Implementation contract address when the token is a proxy (lowercase hex)';
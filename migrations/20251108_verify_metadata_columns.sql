@@ -0,0 +1,58 @@
+-- ============================================
+-- Migration: Verify metadata/tokenmap columns used by the workers
+-- Date: 2025-11-08
+-- Description: Idempotently guarantees every column the sync workers
+--              read or write exists with the expected type, plus the
+--              constraints/indexes their upserts and lookups rely on.
+--              All statements are no-ops on an up-to-date schema.
+-- ============================================
+
+-- metadata: columns written by insert_metadata / force_update_metadata
+-- and update_metadata_from_blockscout
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS tokenid TEXT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS nftid TEXT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS decimals BIGINT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS homepage TEXT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS image TEXT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS description TEXT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS notices JSONB;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS token_type TEXT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS is_verified BOOLEAN;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS risk_level TEXT;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS created_at TIMESTAMP NOT NULL DEFAULT CURRENT_TIMESTAMP;
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS updated_at TIMESTAMP;
+
+-- ON CONFLICT (address, chainid) in the metadata upserts needs this constraint
+DO $$
+BEGIN
+    IF NOT EXISTS (
+        SELECT 1 FROM pg_constraint
+        WHERE conrelid = 'metadata'::regclass
+          AND contype = 'u'
+          AND conname = 'metadata_address_chainid_key'
+    ) THEN
+        ALTER TABLE metadata ADD CONSTRAINT metadata_address_chainid_key UNIQUE (address, chainid);
+    END IF;
+END $$;
+
+-- Chain-scoped lookups (listing by chainid, token detail/history by chainid + address)
+CREATE INDEX IF NOT EXISTS idx_metadata_chainid_address ON metadata(chainid, address);
+
+-- tokenmap: the workers use `tokenid` (matching metadata/nftmap naming),
+-- but the initial schema named it `token_id`
+DO $$
+BEGIN
+    IF EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'tokenmap' AND column_name = 'token_id'
+    ) AND NOT EXISTS (
+        SELECT 1 FROM information_schema.columns
+        WHERE table_name = 'tokenmap' AND column_name = 'tokenid'
+    ) THEN
+        ALTER TABLE tokenmap RENAME COLUMN token_id TO tokenid;
+    END IF;
+END $$;
+
+-- tokenmap/nftmap ids are read as i64 (resume cursors), so store them as BIGINT
+ALTER TABLE tokenmap ALTER COLUMN id TYPE BIGINT;
+ALTER TABLE nftmap ALTER COLUMN id TYPE BIGINT;
@@ -0,0 +1,17 @@
+-- ============================================
+-- Migration: Add developer_data to metadata
+-- Date: 2025-11-09
+-- Description: Raw CoinGecko developer_data object (forks, stars,
+--              commits, ...) plus a derived github_stars column for
+--              sorting by repository popularity
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS developer_data JSONB;
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS github_stars INTEGER
+    GENERATED ALWAYS AS ((developer_data->>'stars')::INTEGER) STORED;
+
+CREATE INDEX IF NOT EXISTS idx_metadata_github_stars ON metadata(github_stars DESC NULLS LAST);
+
+COMMENT ON COLUMN metadata.developer_data IS 'CoinGecko developer_data: forks, stars, subscribers, total_issues, closed_issues, pull_requests_merged, commit_count_4_weeks, last_4_weeks_commit_activity_series';
+COMMENT ON COLUMN metadata.github_stars IS 'Derived from developer_data.stars';
@@ -0,0 +1,20 @@
+-- ============================================
+-- Migration: Create contract_abis table
+-- Date: 2025-11-10
+-- Description: ABI JSON of verified contracts, fetched from Blockscout
+--              /api/v2/smart-contracts/{address}
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS contract_abis (
+    id SERIAL PRIMARY KEY,
+    address TEXT NOT NULL,
+    chainid BIGINT NOT NULL,
+    abi JSONB NOT NULL,
+    fetched_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- One ABI per contract per chain, regardless of address casing
+CREATE UNIQUE INDEX IF NOT EXISTS idx_contract_abis_address_chainid
+ON contract_abis(LOWER(address), chainid);
+
+COMMENT ON TABLE contract_abis IS 'Contract ABIs of Blockscout-verified contracts';
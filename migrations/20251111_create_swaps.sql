@@ -0,0 +1,47 @@
+-- ============================================
+-- Migration: Create pair_tokens and swaps tables
+-- Date: 2025-11-11
+-- Description: UniswapV2 Swap events of configured pairs, with the pair's
+--              token0/token1 resolved from pair_tokens
+-- ============================================
+
+-- Token addresses of each UniswapV2 pair (populated separately)
+CREATE TABLE IF NOT EXISTS pair_tokens (
+    pair_address TEXT NOT NULL,
+    chainid BIGINT NOT NULL,
+    token0 TEXT NOT NULL,
+    token1 TEXT NOT NULL,
+    PRIMARY KEY (pair_address, chainid)
+);
+
+CREATE TABLE IF NOT EXISTS swaps (
+    id BIGSERIAL PRIMARY KEY,
+    chainid BIGINT NOT NULL,
+    pair_address TEXT NOT NULL,
+    sender TEXT NOT NULL,
+    to_address TEXT NOT NULL,
+    -- uint256 amounts do not fit in BIGINT
+    amount0_in NUMERIC(78, 0) NOT NULL,
+    amount1_in NUMERIC(78, 0) NOT NULL,
+    amount0_out NUMERIC(78, 0) NOT NULL,
+    amount1_out NUMERIC(78, 0) NOT NULL,
+    -- NULL when the pair is missing from pair_tokens at index time
+    token0 TEXT,
+    token1 TEXT,
+    block_number BIGINT NOT NULL,
+    tx_hash TEXT NOT NULL,
+    log_index BIGINT NOT NULL,
+    timestamp TIMESTAMPTZ,
+    -- Re-scanned blocks must not duplicate events
+    UNIQUE (chainid, tx_hash, log_index)
+);
+
+CREATE INDEX IF NOT EXISTS idx_swaps_pair_block
+ON swaps(chainid, pair_address, block_number DESC);
+
+CREATE INDEX IF NOT EXISTS idx_swaps_token0 ON swaps(token0);
+CREATE INDEX IF NOT EXISTS idx_swaps_token1 ON swaps(token1);
+
+COMMENT ON TABLE pair_tokens IS 'token0/token1 of indexed UniswapV2 pairs';
+COMMENT ON TABLE swaps IS 'UniswapV2 Swap events of pairs listed in UNISWAP_PAIRS';
+COMMENT ON COLUMN swaps.to_address IS 'Swap event `to` (recipient)';
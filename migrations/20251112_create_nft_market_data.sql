@@ -0,0 +1,16 @@
+-- ============================================
+-- Migration: Create nft_market_data table
+-- Date: 2025-11-12
+-- Description: Floor price, 24h volume and market cap (USD) of NFT
+--              collections, from CoinGecko /api/v3/nfts/{id}
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS nft_market_data (
+    nftid TEXT PRIMARY KEY,
+    floor_price_usd NUMERIC,
+    volume_24h_usd NUMERIC,
+    market_cap_usd NUMERIC,
+    updated_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+COMMENT ON TABLE nft_market_data IS 'Daily NFT collection market metrics from CoinGecko';
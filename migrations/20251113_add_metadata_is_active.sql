@@ -0,0 +1,13 @@
+-- ============================================
+-- Migration: Add is_active flag to metadata
+-- Date: 2025-11-13
+-- Description: Tokens whose CoinGecko ID returns 404 (delisted, merged,
+--              deprecated) are deactivated instead of deleted
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS is_active BOOLEAN NOT NULL DEFAULT true;
+
+-- Deactivation and reactivation look rows up by CoinGecko ID
+CREATE INDEX IF NOT EXISTS idx_metadata_tokenid ON metadata(tokenid);
+
+COMMENT ON COLUMN metadata.is_active IS 'False once the CoinGecko ID is gone; hidden from the read API by default';
@@ -0,0 +1,20 @@
+-- ============================================
+-- Migration: Create audit_log table
+-- Date: 2025-11-14
+-- Description: One row per manager RPC invocation, with credentials
+--              redacted from the params
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS audit_log (
+    id BIGSERIAL PRIMARY KEY,
+    ts TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    source_ip TEXT NOT NULL,
+    method TEXT NOT NULL,
+    params_redacted JSONB,
+    -- {"result": ...} or {"error": {...}}, as returned to the caller
+    result JSONB NOT NULL
+);
+
+CREATE INDEX IF NOT EXISTS idx_audit_log_ts ON audit_log(ts DESC);
+
+COMMENT ON TABLE audit_log IS 'Audit trail of manager RPC calls';
@@ -0,0 +1,13 @@
+-- ============================================
+-- Migration: Create health_check_log table
+-- Date: 2025-11-15
+-- Description: Scratch table for the health_check_db manager RPC
+--              (rows are deleted right after being read back)
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS health_check_log (
+    id BIGSERIAL PRIMARY KEY,
+    checked_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+COMMENT ON TABLE health_check_log IS 'Write-read-delete probe rows of health_check_db; normally empty';
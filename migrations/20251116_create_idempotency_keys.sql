@@ -0,0 +1,20 @@
+-- ============================================
+-- Migration: Create idempotency_keys table
+-- Date: 2025-11-16
+-- Description: Results of manager RPC calls sent with an idempotency_key,
+--              replayed when a client retries the same key
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS idempotency_keys (
+    key TEXT PRIMARY KEY,
+    method TEXT NOT NULL,
+    -- Result of the first successful execution; NULL while the call that
+    -- claimed the key is still running
+    result JSONB,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP
+);
+
+-- Expired keys are pruned by created_at
+CREATE INDEX IF NOT EXISTS idx_idempotency_keys_created_at ON idempotency_keys(created_at);
+
+COMMENT ON TABLE idempotency_keys IS 'Replay cache of manager RPC results, keyed by client-chosen idempotency_key';
@@ -0,0 +1,14 @@
+-- ============================================
+-- Migration: Add platforms to metadata
+-- Date: 2025-11-17
+-- Description: CoinGecko platforms map ({platform slug: address}) of every
+--              chain a token is deployed on, for cross-chain lookups such
+--              as WHERE platforms ? 'ethereum'
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS platforms JSONB;
+
+-- jsonb_ops supports the key-existence operators (?, ?|, ?&)
+CREATE INDEX IF NOT EXISTS idx_metadata_platforms ON metadata USING GIN (platforms);
+
+COMMENT ON COLUMN metadata.platforms IS 'CoinGecko platforms: platform slug -> contract address (EVM addresses lowercased)';
@@ -0,0 +1,16 @@
+-- ============================================
+-- Migration: Create indexed_contracts table
+-- Date: 2025-11-18
+-- Description: Contracts whose on-chain events the subscriber indexes,
+--              registered at runtime via the add_indexed_contract RPC
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS indexed_contracts (
+    chainid BIGINT NOT NULL,
+    -- Lowercased 0x address
+    address TEXT NOT NULL,
+    created_at TIMESTAMPTZ NOT NULL DEFAULT CURRENT_TIMESTAMP,
+    PRIMARY KEY (chainid, address)
+);
+
+COMMENT ON TABLE indexed_contracts IS 'Contracts indexed by the event subscriber, per chain';
@@ -0,0 +1,16 @@
+-- ============================================
+-- Migration: Add community_data to metadata
+-- Date: 2025-11-19
+-- Description: CoinGecko community counters (twitter_followers,
+--              reddit_subscribers, telegram_channel_user_count, ...) used
+--              as an adoption signal by GET /metadata/trending
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS community_data JSONB;
+
+-- Backs ORDER BY ... DESC of the default trending sort; the indexer only
+-- stores integer counters under this key, so the cast cannot fail
+CREATE INDEX IF NOT EXISTS idx_metadata_twitter_followers
+ON metadata (((community_data->>'twitter_followers')::bigint));
+
+COMMENT ON COLUMN metadata.community_data IS 'CoinGecko community_data counters (numeric values only)';
@@ -0,0 +1,10 @@
+-- ============================================
+-- Migration: Add tickers to metadata
+-- Date: 2025-11-20
+-- Description: Top 10 CoinGecko exchange tickers by volume of each token
+--              (only filled when FETCH_TICKERS=true)
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS tickers JSONB;
+
+COMMENT ON COLUMN metadata.tickers IS 'Top exchange tickers by volume: [{exchange, base, target, last, volume}]';
@@ -0,0 +1,14 @@
+-- ============================================
+-- Migration: Add holder_count to metadata
+-- Date: 2025-11-21
+-- Description: Token holder count reported by Blockscout, a proxy for
+--              adoption used to filter out low-usage tokens
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS holder_count BIGINT;
+
+-- Backs GET /metadata/top-holders-count (per chain, most holders first)
+CREATE INDEX IF NOT EXISTS idx_metadata_chainid_holder_count
+ON metadata(chainid, holder_count DESC NULLS LAST);
+
+COMMENT ON COLUMN metadata.holder_count IS 'Token holders according to Blockscout (NULL if not reported)';
@@ -0,0 +1,16 @@
+-- ============================================
+-- Migration: Add delisted_at to tokenmap
+-- Date: 2025-11-22
+-- Description: Set when CoinGecko answers 404 for the token ID; the
+--              metadata sync skips delisted entries until the ID shows
+--              up in /coins/list again
+-- ============================================
+
+ALTER TABLE tokenmap ADD COLUMN IF NOT EXISTS delisted_at TIMESTAMPTZ;
+
+-- Backs list_delisted_tokens (newest first)
+CREATE INDEX IF NOT EXISTS idx_tokenmap_delisted_at
+ON tokenmap(delisted_at DESC)
+WHERE delisted_at IS NOT NULL;
+
+COMMENT ON COLUMN tokenmap.delisted_at IS 'When CoinGecko stopped listing the token ID (NULL while listed)';
@@ -0,0 +1,11 @@
+-- ============================================
+-- Migration: Add implementation_verified to metadata
+-- Date: 2025-11-23
+-- Description: Blockscout verification status of a proxy's implementation
+--              contract; an unverified implementation hides what the
+--              upgradeable token actually runs
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS implementation_verified BOOLEAN;
+
+COMMENT ON COLUMN metadata.implementation_verified IS 'Whether proxy_implementation is verified on Blockscout (NULL for non-proxies or if unknown)';
@@ -0,0 +1,15 @@
+-- ============================================
+-- Migration: Create task_runs table
+-- Date: 2025-11-24
+-- Description: Last successful run of each background sync, so a
+--              scheduled run right after a manual one (or a restart) can
+--              be skipped
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS task_runs (
+    -- Task name (e.g. sync_marketdata, update_forex)
+    task TEXT PRIMARY KEY,
+    last_success_at TIMESTAMPTZ NOT NULL
+);
+
+COMMENT ON TABLE task_runs IS 'Last successful run per background sync task';
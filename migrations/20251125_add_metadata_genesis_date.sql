@@ -0,0 +1,14 @@
+-- ============================================
+-- Migration: Add genesis_date to metadata
+-- Date: 2025-11-25
+-- Description: Launch date reported by CoinGecko /coins/{id}, used to
+--              filter out very new tokens (GET /tokens?min_age_days=)
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS genesis_date DATE;
+
+CREATE INDEX IF NOT EXISTS idx_metadata_genesis_date
+ON metadata(genesis_date)
+WHERE genesis_date IS NOT NULL;
+
+COMMENT ON COLUMN metadata.genesis_date IS 'Genesis date according to CoinGecko (NULL if not reported)';
@@ -0,0 +1,20 @@
+-- ============================================
+-- Migration: Create watchlist table
+-- Date: 2025-11-26
+-- Description: Tokens monitored more closely; their metadata is re-fetched
+--              on every daily sync even when it already exists
+-- ============================================
+
+CREATE TABLE IF NOT EXISTS watchlist (
+    id BIGSERIAL PRIMARY KEY,
+    -- CoinGecko token ID from tokenmap at the time of adding (NULL if unmapped)
+    tokenid TEXT,
+    chainid BIGINT NOT NULL,
+    -- Stored lowercase
+    address TEXT NOT NULL,
+    notes TEXT,
+    added_at TIMESTAMPTZ NOT NULL DEFAULT NOW(),
+    UNIQUE (address, chainid)
+);
+
+COMMENT ON TABLE watchlist IS 'Tokens whose metadata is refreshed on every daily sync';
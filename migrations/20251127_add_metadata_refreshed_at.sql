@@ -0,0 +1,16 @@
+-- ============================================
+-- Migration: Add refreshed_at to metadata
+-- Date: 2025-11-27
+-- Description: When a token's metadata was last re-fetched from CoinGecko
+--              /coins/{id}. The daily refresh re-checks rows older than
+--              METADATA_REFRESH_DAYS (oldest first, NULL = never checked),
+--              updating them or deactivating them on a 404.
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS refreshed_at TIMESTAMP;
+
+CREATE INDEX IF NOT EXISTS idx_metadata_refreshed_at
+ON metadata(refreshed_at NULLS FIRST)
+WHERE tokenid IS NOT NULL AND is_active;
+
+COMMENT ON COLUMN metadata.refreshed_at IS 'Last CoinGecko re-check of the token (NULL if never re-checked)';
@@ -0,0 +1,20 @@
+-- ============================================
+-- Migration: Backfill metadata.platforms from tokenmap
+-- Date: 2025-11-28
+-- Description: Rows fetched before platforms was stored have it NULL, so
+--              cross-chain lookups (WHERE platforms ? 'ethereum') missed
+--              them. Seeds the map from the tokenmap entries of the same
+--              CoinGecko ID on indexed chains; the metadata refresh later
+--              replaces it with the full map from /coins/{id}.
+-- ============================================
+
+UPDATE metadata m
+SET platforms = seeded.platforms
+FROM (
+    SELECT t.tokenid, jsonb_object_agg(c.name, t.address) AS platforms
+    FROM tokenmap t
+    JOIN chains c ON c.chainid = t.chainid
+    GROUP BY t.tokenid
+) seeded
+WHERE m.tokenid = seeded.tokenid
+    AND m.platforms IS NULL;
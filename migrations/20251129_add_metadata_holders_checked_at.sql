@@ -0,0 +1,12 @@
+-- ============================================
+-- Migration: Add holders_checked_at to metadata
+-- Date: 2025-11-29
+-- Description: When Blockscout was last asked for an ERC-20's holder
+--              count. The Blockscout sync re-checks counts older than a
+--              week instead of only filling in missing ones, so
+--              holder_count no longer freezes at its first value.
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS holders_checked_at TIMESTAMP;
+
+COMMENT ON COLUMN metadata.holders_checked_at IS 'Last Blockscout holder count check (NULL if never checked)';
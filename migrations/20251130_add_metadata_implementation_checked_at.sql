@@ -0,0 +1,12 @@
+-- ============================================
+-- Migration: Add implementation_checked_at to metadata
+-- Date: 2025-11-30
+-- Description: When Blockscout was last asked whether a proxy's
+--              implementation is verified. A lookup that fails leaves
+--              implementation_verified NULL; the Blockscout sync retries
+--              it weekly instead of on every run.
+-- ============================================
+
+ALTER TABLE metadata ADD COLUMN IF NOT EXISTS implementation_checked_at TIMESTAMP;
+
+COMMENT ON COLUMN metadata.implementation_checked_at IS 'Last implementation verification lookup (NULL if never attempted)';
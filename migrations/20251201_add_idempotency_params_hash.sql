@@ -0,0 +1,12 @@
+-- ============================================
+-- Migration: Add params_hash to idempotency_keys
+-- Date: 2025-12-01
+-- Description: MD5 of the canonical JSONB params of the call that claimed
+--              a key, so reusing the key with different params is rejected
+--              instead of replaying an unrelated result. Only the hash is
+--              kept since params may carry credentials (e.g. new_url).
+-- ============================================
+
+ALTER TABLE idempotency_keys ADD COLUMN IF NOT EXISTS params_hash TEXT;
+
+COMMENT ON COLUMN idempotency_keys.params_hash IS 'md5 of the call params as JSONB text (NULL for keys claimed before it was stored)';
@@ -0,0 +1,1167 @@
+//! Public Read API
+//!
+//! HTTP handlers for querying indexed data. Unlike the manager RPC these
+//! endpoints require no authentication and never modify state.
+//!
+//! # Endpoints
+//! - `GET /token/{chainid}/{address}` - Token metadata (ETag / `If-None-Match` aware)
+//! - `GET /token/{chainid}/{address}/history` - Symbol/name change history
+//! - `GET /market_data/{tokenid}/sparkline` - 7-day hourly price series
+//! - `GET /tokens` - Paginated, filterable token catalog (ETag aware)
+//! - `GET /search` - Ranked full-text token search
+//! - `GET /metadata/trending` - Tokens ranked by a CoinGecko community counter
+//! - `GET /metadata/top-holders-count` - Tokens ranked by Blockscout holder count
+//! - `GET /nfts` - Paginated NFT collection catalog
+//! - `GET /nft/{chainid}/{address}` - NFT collection metadata (ETag / `If-None-Match` aware)
+
+use crate::config::Config;
+use crate::worker::metadata::COMMUNITY_SORT_KEYS;
+use axum::{
+    Json, Router,
+    extract::{Path, Query, State},
+    http::{HeaderMap, HeaderValue, Method, StatusCode, header},
+    response::{IntoResponse, Response},
+    routing::get,
+};
+use chrono::{DateTime, NaiveDate, NaiveDateTime, Utc};
+use serde::{Deserialize, Serialize};
+use sqlx::{Postgres, QueryBuilder};
+use serde_json::{Value, json};
+use std::hash::{DefaultHasher, Hash, Hasher};
+use std::sync::Arc;
+use tokio::sync::RwLock;
+use tower_http::compression::CompressionLayer;
+use tower_http::cors::{AllowOrigin, CorsLayer};
+use tracing::{error, warn};
+
+/// Default page size for list endpoints
+const DEFAULT_LIMIT: i64 = 50;
+/// Maximum page size for list endpoints
+const MAX_LIMIT: i64 = 200;
+/// Default number of search results
+const DEFAULT_SEARCH_LIMIT: i64 = 20;
+/// Default number of trending tokens
+const DEFAULT_TRENDING_LIMIT: i64 = 20;
+/// Queries shorter than this (in characters) use the trigram ILIKE fallback
+const MIN_FULL_TEXT_QUERY_LEN: usize = 3;
+
+/// Builds the read API routes
+///
+/// Responses are gzip/brotli compressed according to `Accept-Encoding`,
+/// and CORS (including `OPTIONS` preflight) is answered for `allowed_origins`.
+/// Both layers only wrap these routes: `/manager` intentionally stays
+/// same-origin with no CORS headers, so browsers can never call it
+/// cross-site.
+pub fn router(allowed_origins: &[String]) -> Router<Arc<RwLock<Config>>> {
+    Router::new()
+        .route("/token/{chainid}/{address}", get(token_detail))
+        .route("/token/{chainid}/{address}/history", get(token_history))
+        .route("/market_data/{tokenid}/sparkline", get(market_data_sparkline))
+        .route("/tokens", get(list_tokens))
+        .route("/search", get(search))
+        .route("/metadata/trending", get(trending_tokens))
+        .route("/metadata/top-holders-count", get(top_holders_count))
+        .route("/nfts", get(list_nfts))
+        .route("/nft/{chainid}/{address}", get(nft_detail))
+        .layer(CompressionLayer::new())
+        .layer(cors_layer(allowed_origins))
+}
+
+/// Builds the CORS layer for the read API
+///
+/// `*` allows any origin (development only); otherwise only the listed
+/// origins are echoed back. Invalid origins are skipped with a warning.
+fn cors_layer(allowed_origins: &[String]) -> CorsLayer {
+    let allow_origin = if allowed_origins.iter().any(|o| o == "*") {
+        AllowOrigin::any()
+    } else {
+        let origins: Vec<HeaderValue> = allowed_origins
+            .iter()
+            .filter_map(|o| match HeaderValue::from_str(o) {
+                Ok(v) => Some(v),
+                Err(_) => {
+                    warn!("⚠️ Ignoring invalid CORS origin: {}", o);
+                    None
+                }
+            })
+            .collect();
+        AllowOrigin::list(origins)
+    };
+
+    CorsLayer::new()
+        .allow_origin(allow_origin)
+        .allow_methods([Method::GET, Method::OPTIONS])
+        .allow_headers([header::CONTENT_TYPE, header::IF_NONE_MATCH])
+        .expose_headers([header::ETAG])
+}
+
+/// Handler result: JSON body on success, status code plus `{"error": ...}` otherwise
+pub type ApiResult<T> = Result<Json<T>, (StatusCode, Json<Value>)>;
+
+/// Builds an error response with a JSON `{"error": message}` body
+pub fn api_error(status: StatusCode, message: impl Into<String>) -> (StatusCode, Json<Value>) {
+    (status, Json(json!({"error": message.into()})))
+}
+
+/// Logs a database error and maps it to a 500 response
+fn internal_error(e: sqlx::Error) -> (StatusCode, Json<Value>) {
+    error!("❌ API query failed: {}", e);
+    api_error(StatusCode::INTERNAL_SERVER_ERROR, "Internal database error")
+}
+
+// ======================= ETag =======================
+
+/// Builds a weak ETag (`W/"..."`) from any hashable version key
+///
+/// The key should change whenever the response would, e.g. a row's id
+/// plus its `updated_at`.
+pub fn weak_etag(key: impl Hash) -> String {
+    let mut hasher = DefaultHasher::new();
+    key.hash(&mut hasher);
+    format!("W/\"{:016x}\"", hasher.finish())
+}
+
+/// Returns whether the request's `If-None-Match` matches `etag`
+///
+/// Uses weak comparison (the `W/` prefix is ignored) and honors `*`
+/// and comma-separated lists.
+fn etag_matches(headers: &HeaderMap, etag: &str) -> bool {
+    let opaque = |tag: &str| tag.trim().trim_start_matches("W/").to_string();
+    let wanted = opaque(etag);
+
+    headers
+        .get_all(header::IF_NONE_MATCH)
+        .iter()
+        .filter_map(|v| v.to_str().ok())
+        .flat_map(|v| v.split(','))
+        .any(|tag| tag.trim() == "*" || opaque(tag) == wanted)
+}
+
+/// Responds with 304 if the client already has `etag`, otherwise with
+/// the JSON body; both carry the `ETag` header
+pub fn json_with_etag(headers: &HeaderMap, etag: &str, body: impl Serialize) -> Response {
+    let etag_header = HeaderValue::from_str(etag).expect("weak_etag output is a valid header");
+
+    if etag_matches(headers, etag) {
+        return (StatusCode::NOT_MODIFIED, [(header::ETAG, etag_header)]).into_response();
+    }
+    ([(header::ETAG, etag_header)], Json(body)).into_response()
+}
+
+// ======================= Token Detail =======================
+
+/// Full metadata of one token
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TokenDetail {
+    /// Database row ID
+    #[serde(skip)]
+    pub id: i32,
+    /// Chain ID
+    pub chainid: i64,
+    /// Contract address (lowercase hex)
+    pub address: String,
+    /// CoinGecko token ID (fungible tokens)
+    pub tokenid: Option<String>,
+    /// CoinGecko NFT collection ID
+    pub nftid: Option<String>,
+    /// Token standard (ERC-20, ERC-721, ...)
+    pub token_type: Option<String>,
+    /// Token symbol
+    pub symbol: String,
+    /// Token name
+    pub name: String,
+    /// Token decimals
+    pub decimals: Option<i64>,
+    /// Project homepage
+    pub homepage: Option<String>,
+    /// Logo URL
+    pub image: Option<String>,
+    /// Project description
+    pub description: Option<String>,
+    /// CoinGecko notices
+    pub notices: Option<sqlx::types::Json<Value>>,
+    /// Social/contact links
+    pub social_links: Option<sqlx::types::Json<Value>>,
+    /// CoinGecko developer statistics (stars, forks, commits, ...)
+    pub developer_data: Option<sqlx::types::Json<Value>>,
+    /// GitHub stars (derived from `developer_data`)
+    pub github_stars: Option<i32>,
+    /// Addresses on every chain the token is deployed on (`{platform: address}`)
+    pub platforms: Option<sqlx::types::Json<Value>>,
+    /// CoinGecko community counters (twitter_followers, reddit_subscribers, ...)
+    pub community_data: Option<sqlx::types::Json<Value>>,
+    /// Top exchange tickers by volume (`[{exchange, base, target, last, volume}]`)
+    pub tickers: Option<sqlx::types::Json<Value>>,
+    /// Launch date according to CoinGecko
+    pub genesis_date: Option<NaiveDate>,
+    /// Token holders according to Blockscout
+    pub holder_count: Option<i64>,
+    /// Contract verification status
+    pub is_verified: Option<bool>,
+    /// Risk bucket (low/medium/high)
+    pub risk_level: Option<String>,
+    /// Risk score 0-100
+    pub risk_score: Option<i16>,
+    /// Whether the contract is a proxy
+    pub is_proxy: Option<bool>,
+    /// Proxy implementation address
+    pub proxy_implementation: Option<String>,
+    /// Whether the implementation contract is verified (proxies only)
+    pub implementation_verified: Option<bool>,
+    /// False once the token is gone from CoinGecko (delisted, merged, ...)
+    pub is_active: bool,
+    /// When the row was created
+    pub created_at: NaiveDateTime,
+    /// When the row was last updated
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Returns the metadata of one token
+///
+/// # Path Parameters
+/// * `chainid` - Chain ID
+/// * `address` - Contract address (case-insensitive)
+///
+/// # Returns
+/// JSON [`TokenDetail`] with a weak `ETag`; 304 if `If-None-Match`
+/// matches, 404 if the token is unknown. Inactive tokens are returned
+/// too (with `is_active: false`), since the caller asked for this contract.
+pub async fn token_detail(
+    State(config): State<Arc<RwLock<Config>>>,
+    Path((chainid, address)): Path<(i64, String)>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let pool = config.read().await.postgres_db.read_pool().clone();
+
+    let token = sqlx::query_as::<_, TokenDetail>(
+        r#"
+        SELECT
+            id, chainid, address, tokenid, nftid, token_type, symbol, name, decimals,
+            homepage, image, description, notices, social_links, developer_data, github_stars, platforms,
+            community_data, tickers, genesis_date, holder_count, is_verified, risk_level, risk_score, is_proxy, proxy_implementation,
+            implementation_verified, is_active, created_at, updated_at
+        FROM metadata
+        WHERE chainid = $1 AND address = $2
+        "#,
+    )
+    .bind(chainid)
+    .bind(address.to_lowercase())
+    .fetch_optional(&pool)
+    .await
+    .map_err(internal_error)?
+    .ok_or_else(|| api_error(StatusCode::NOT_FOUND, "Token not found"))?;
+
+    let etag = weak_etag((token.id, token.updated_at.unwrap_or(token.created_at)));
+    Ok(json_with_etag(&headers, &etag, token))
+}
+
+// ======================= Token History =======================
+
+/// One recorded change of a metadata field
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct MetadataChange {
+    /// Changed column (`symbol` or `name`)
+    pub field: String,
+    /// Value before the change
+    pub old_value: Option<String>,
+    /// Value after the change
+    pub new_value: Option<String>,
+    /// When the change was detected
+    pub changed_at: DateTime<Utc>,
+}
+
+/// Returns the symbol/name change history of a token, newest first
+///
+/// # Path Parameters
+/// * `chainid` - Chain ID
+/// * `address` - Contract address (case-insensitive)
+///
+/// # Returns
+/// JSON array of [`MetadataChange`]; empty if the token never changed
+pub async fn token_history(
+    State(config): State<Arc<RwLock<Config>>>,
+    Path((chainid, address)): Path<(i64, String)>,
+) -> ApiResult<Vec<MetadataChange>> {
+    let pool = config.read().await.postgres_db.read_pool().clone();
+
+    let changes = sqlx::query_as::<_, MetadataChange>(
+        r#"
+        SELECT field, old_value, new_value, changed_at
+        FROM metadata_history
+        WHERE chainid = $1 AND address = $2
+        ORDER BY changed_at DESC, id DESC
+        "#,
+    )
+    .bind(chainid)
+    .bind(address.to_lowercase())
+    .fetch_all(&pool)
+    .await
+    .map_err(internal_error)?;
+
+    Ok(Json(changes))
+}
+
+// ======================= Market Data =======================
+
+/// Returns the stored 7-day sparkline of a CoinGecko token
+///
+/// # Path Parameters
+/// * `tokenid` - CoinGecko token ID (e.g., "ethereum")
+///
+/// # Returns
+/// JSON array of hourly USD prices, oldest first.
+/// 404 if the token is unknown or no sparkline was stored
+/// (sparklines are only fetched with `FETCH_SPARKLINE=true`).
+pub async fn market_data_sparkline(
+    State(config): State<Arc<RwLock<Config>>>,
+    Path(tokenid): Path<String>,
+) -> ApiResult<Vec<f64>> {
+    let pool = config.read().await.postgres_db.read_pool().clone();
+
+    let sparkline: Option<Option<sqlx::types::Json<Vec<f64>>>> = sqlx::query_scalar(
+        "SELECT sparkline_7d FROM marketdata WHERE token_id = $1 LIMIT 1",
+    )
+    .bind(&tokenid)
+    .fetch_optional(&pool)
+    .await
+    .map_err(internal_error)?;
+
+    match sparkline.flatten() {
+        Some(sqlx::types::Json(prices)) => Ok(Json(prices)),
+        None => Err(api_error(
+            StatusCode::NOT_FOUND,
+            format!("No sparkline for token {}", tokenid),
+        )),
+    }
+}
+
+// ======================= Token Listing =======================
+
+/// Query parameters of `GET /tokens`
+#[derive(Debug, Default, Deserialize)]
+pub struct TokenListParams {
+    /// Only tokens on this chain
+    pub chainid: Option<i64>,
+    /// Only tokens with this contract verification status
+    pub verified: Option<bool>,
+    /// Case-insensitive substring match on symbol or name
+    pub q: Option<String>,
+    /// Page size (default 50, capped at 200)
+    pub limit: Option<i64>,
+    /// Rows to skip
+    pub offset: Option<i64>,
+    /// Sort key: `market_cap` (default), `market_cap_rank`, `github_stars`, `symbol` or `name`
+    pub order: Option<String>,
+    /// Also list tokens deactivated after disappearing from CoinGecko (default false)
+    pub include_inactive: Option<bool>,
+    /// Leave out tokens whose CoinGecko genesis date is at most this many days ago
+    ///
+    /// A NULL genesis date counts as unknown, not new, so such tokens are kept
+    /// (CoinGecko reports none for many coins, and rows stored before the
+    /// column existed only get one from the metadata refresh).
+    pub min_age_days: Option<i32>,
+}
+
+/// One row of the token catalog
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TokenListItem {
+    /// Chain ID
+    pub chainid: i64,
+    /// Contract address (lowercase hex)
+    pub address: String,
+    /// CoinGecko token ID
+    pub tokenid: Option<String>,
+    /// Token symbol
+    pub symbol: String,
+    /// Token name
+    pub name: String,
+    /// Token decimals
+    pub decimals: Option<i64>,
+    /// Logo URL
+    pub image: Option<String>,
+    /// Token standard (ERC-20, ERC-721, ...)
+    pub token_type: Option<String>,
+    /// Contract verification status
+    pub is_verified: Option<bool>,
+    /// Risk bucket (low/medium/high)
+    pub risk_level: Option<String>,
+    /// Risk score 0-100
+    pub risk_score: Option<i16>,
+    /// Whether the contract is a proxy
+    pub is_proxy: Option<bool>,
+    /// Proxy implementation address
+    pub proxy_implementation: Option<String>,
+    /// GitHub stars of the project repository
+    pub github_stars: Option<i32>,
+    /// Market cap in USD
+    pub market_cap: Option<f64>,
+    /// Market cap rank
+    pub market_cap_rank: Option<i64>,
+    /// Last metadata change (creation time if never updated)
+    pub updated_at: NaiveDateTime,
+}
+
+/// Maps a user-supplied sort key to a fixed ORDER BY clause
+///
+/// Only whitelisted keys are accepted, so the clause can be pushed into
+/// the query verbatim.
+fn order_clause(order: Option<&str>) -> Option<&'static str> {
+    match order.unwrap_or("market_cap") {
+        "market_cap" => Some("md.market_cap DESC NULLS LAST, m.id"),
+        "market_cap_rank" => Some("md.market_cap_rank ASC NULLS LAST, m.id"),
+        "github_stars" => Some("m.github_stars DESC NULLS LAST, m.id"),
+        "symbol" => Some("m.symbol, m.id"),
+        "name" => Some("m.name, m.id"),
+        _ => None,
+    }
+}
+
+/// Escapes `%`, `_` and `\` so user input matches literally inside `ILIKE`
+fn escape_like(input: &str) -> String {
+    let mut escaped = String::with_capacity(input.len());
+    for c in input.chars() {
+        if matches!(c, '%' | '_' | '\\') {
+            escaped.push('\\');
+        }
+        escaped.push(c);
+    }
+    escaped
+}
+
+/// Clamps the requested page size into `1..=MAX_LIMIT`
+fn clamp_limit(limit: Option<i64>) -> i64 {
+    limit.unwrap_or(DEFAULT_LIMIT).clamp(1, MAX_LIMIT)
+}
+
+/// Builds the catalog query for the given filters
+///
+/// All user values are bound as parameters; only the whitelisted ORDER BY
+/// clause is pushed as SQL.
+fn build_token_list_query<'a>(
+    params: &'a TokenListParams,
+    order: &'static str,
+) -> QueryBuilder<'a, Postgres> {
+    let mut qb = QueryBuilder::<Postgres>::new(
+        r#"
+        SELECT
+            m.chainid, m.address, m.tokenid, m.symbol, m.name, m.decimals, m.image,
+            m.token_type, m.is_verified, m.risk_level, m.risk_score,
+            m.is_proxy, m.proxy_implementation, m.github_stars,
+            md.market_cap, md.market_cap_rank,
+            COALESCE(m.updated_at, m.created_at) AS updated_at
+        FROM metadata m
+        LEFT JOIN LATERAL (
+            SELECT market_cap, market_cap_rank FROM marketdata WHERE token_id = m.tokenid LIMIT 1
+        ) md ON TRUE
+        "#,
+    );
+
+    if params.include_inactive.unwrap_or(false) {
+        qb.push(" WHERE TRUE");
+    } else {
+        qb.push(" WHERE m.is_active");
+    }
+
+    if let Some(chainid) = params.chainid {
+        qb.push(" AND m.chainid = ").push_bind(chainid);
+    }
+    if let Some(verified) = params.verified {
+        qb.push(" AND m.is_verified = ").push_bind(verified);
+    }
+    if let Some(days) = params.min_age_days {
+        qb.push(" AND (m.genesis_date IS NULL OR m.genesis_date < NOW() - make_interval(days => ")
+            .push_bind(days.max(0))
+            .push("))");
+    }
+    if let Some(q) = params.q.as_deref().map(str::trim).filter(|q| !q.is_empty()) {
+        let pattern = format!("%{}%", escape_like(q));
+        qb.push(" AND (m.symbol ILIKE ")
+            .push_bind(pattern.clone())
+            .push(" OR m.name ILIKE ")
+            .push_bind(pattern)
+            .push(")");
+    }
+
+    qb.push(" ORDER BY ").push(order);
+    qb.push(" LIMIT ").push_bind(clamp_limit(params.limit));
+    qb.push(" OFFSET ").push_bind(params.offset.unwrap_or(0).max(0));
+    qb
+}
+
+/// Lists tokens with optional filtering, sorting and pagination
+///
+/// # Query Parameters
+/// See [`TokenListParams`], e.g. `/tokens?chainid=1&verified=true&limit=50&offset=0&order=market_cap`.
+/// Deactivated tokens are hidden unless `include_inactive=true`.
+///
+/// # Returns
+/// `{"items": [...], "limit": n, "offset": n}` with a weak `ETag`;
+/// 304 if `If-None-Match` matches, 400 on an unknown `order`
+pub async fn list_tokens(
+    State(config): State<Arc<RwLock<Config>>>,
+    Query(params): Query<TokenListParams>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let Some(order) = order_clause(params.order.as_deref()) else {
+        return Err(api_error(
+            StatusCode::BAD_REQUEST,
+            "Invalid order: expected market_cap, market_cap_rank, github_stars, symbol or name",
+        ));
+    };
+
+    let pool = config.read().await.postgres_db.read_pool().clone();
+
+    let items: Vec<TokenListItem> = build_token_list_query(&params, order)
+        .build_query_as()
+        .fetch_all(&pool)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(json_with_etag(
+        &headers,
+        &token_list_etag(&items),
+        json!({
+            "items": items,
+            "limit": clamp_limit(params.limit),
+            "offset": params.offset.unwrap_or(0).max(0),
+        }),
+    ))
+}
+
+/// ETag of a listing page: newest `updated_at` plus which rows are on the
+/// page and their market caps (market data is refreshed independently of
+/// metadata)
+fn token_list_etag(items: &[TokenListItem]) -> String {
+    let max_updated = items.iter().map(|t| t.updated_at).max();
+    let rows: Vec<(i64, &str, Option<u64>)> = items
+        .iter()
+        .map(|t| (t.chainid, t.address.as_str(), t.market_cap.map(f64::to_bits)))
+        .collect();
+    weak_etag((max_updated, rows))
+}
+
+// ======================= Search =======================
+
+/// Query parameters of `GET /search`
+#[derive(Debug, Deserialize)]
+pub struct SearchParams {
+    /// Search text
+    pub q: String,
+    /// Maximum results (default 20, capped at 200)
+    pub limit: Option<i64>,
+}
+
+/// One ranked search hit
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct SearchResult {
+    /// Chain ID
+    pub chainid: i64,
+    /// Contract address (lowercase hex)
+    pub address: String,
+    /// Token symbol
+    pub symbol: String,
+    /// Token name
+    pub name: String,
+    /// Relevance (higher is better)
+    pub rank: f32,
+}
+
+/// Turns free text into a prefix-matching tsquery, e.g. `"wrapped eth"` -> `"wrapped:* & eth:*"`
+///
+/// Everything except letters and digits is treated as a separator, so the
+/// result never contains tsquery operators from user input.
+fn prefix_tsquery(q: &str) -> Option<String> {
+    let terms: Vec<String> = q
+        .split(|c: char| !c.is_alphanumeric())
+        .filter(|t| !t.is_empty())
+        .map(|t| format!("{}:*", t.to_lowercase()))
+        .collect();
+
+    (!terms.is_empty()).then(|| terms.join(" & "))
+}
+
+/// Searches tokens by symbol, name and description
+///
+/// Queries of 3+ characters use the `search_vector` full-text index with
+/// prefix matching (good for autocomplete), ranked by `ts_rank` with symbol
+/// matches weighted highest. Shorter queries fall back to a trigram-indexed
+/// `ILIKE` prefix match on symbol/name.
+///
+/// # Query Parameters
+/// * `q` - Search text (required)
+/// * `limit` - Maximum results (default 20, capped at 200)
+///
+/// # Returns
+/// JSON array of [`SearchResult`] (active tokens only), best match first;
+/// 400 on an empty query
+pub async fn search(
+    State(config): State<Arc<RwLock<Config>>>,
+    Query(params): Query<SearchParams>,
+) -> ApiResult<Vec<SearchResult>> {
+    let q = params.q.trim();
+    if q.is_empty() {
+        return Err(api_error(StatusCode::BAD_REQUEST, "Query parameter q must not be empty"));
+    }
+    let limit = params.limit.unwrap_or(DEFAULT_SEARCH_LIMIT).clamp(1, MAX_LIMIT);
+
+    let pool = config.read().await.postgres_db.read_pool().clone();
+
+    let tsquery = prefix_tsquery(q).filter(|_| q.chars().count() >= MIN_FULL_TEXT_QUERY_LEN);
+    let results = match tsquery {
+        Some(tsquery) => {
+            sqlx::query_as::<_, SearchResult>(
+                r#"
+                SELECT chainid, address, symbol, name,
+                       ts_rank(search_vector, to_tsquery('simple', $1)) AS rank
+                FROM metadata
+                WHERE search_vector @@ to_tsquery('simple', $1) AND is_active
+                ORDER BY rank DESC, id
+                LIMIT $2
+                "#,
+            )
+            .bind(tsquery)
+            .bind(limit)
+            .fetch_all(&pool)
+            .await
+        }
+        None => {
+            // Exact symbol matches first, then prefix matches
+            sqlx::query_as::<_, SearchResult>(
+                r#"
+                SELECT chainid, address, symbol, name,
+                       (CASE WHEN LOWER(symbol) = LOWER($1) THEN 1.0 ELSE 0.5 END)::REAL AS rank
+                FROM metadata
+                WHERE (symbol ILIKE $2 OR name ILIKE $2) AND is_active
+                ORDER BY rank DESC, LENGTH(symbol), id
+                LIMIT $3
+                "#,
+            )
+            .bind(q)
+            .bind(format!("{}%", escape_like(q)))
+            .bind(limit)
+            .fetch_all(&pool)
+            .await
+        }
+    }
+    .map_err(internal_error)?;
+
+    Ok(Json(results))
+}
+
+// ======================= Trending =======================
+
+/// Query parameters of `GET /metadata/trending`
+#[derive(Debug, Default, Deserialize)]
+pub struct TrendingParams {
+    /// Counter to rank by (see [`COMMUNITY_SORT_KEYS`]), default `twitter_followers`
+    pub sort: Option<String>,
+    /// Maximum results (default 20, capped at 200)
+    pub limit: Option<i64>,
+}
+
+/// One token of the trending ranking
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct TrendingToken {
+    /// Chain ID
+    pub chainid: i64,
+    /// Contract address (lowercase hex)
+    pub address: String,
+    /// CoinGecko token ID
+    pub tokenid: Option<String>,
+    /// Token symbol
+    pub symbol: String,
+    /// Token name
+    pub name: String,
+    /// Logo URL
+    pub image: Option<String>,
+    /// Value of the sort counter
+    pub count: i64,
+    /// All community counters of the token
+    pub community_data: Option<sqlx::types::Json<Value>>,
+    /// When the counters were last fetched from CoinGecko
+    pub refreshed_at: NaiveDateTime,
+}
+
+/// Maps a sort key to its `community_data` expression
+///
+/// The `twitter_followers` expression matches the functional index of the
+/// `community_data` migration, so the default sort can use it.
+fn community_sort_expr(sort: Option<&str>) -> Option<&'static str> {
+    match sort.unwrap_or("twitter_followers") {
+        "twitter_followers" => Some("(m.community_data->>'twitter_followers')::bigint"),
+        "reddit_subscribers" => Some("(m.community_data->>'reddit_subscribers')::bigint"),
+        "telegram_channel_user_count" => Some("(m.community_data->>'telegram_channel_user_count')::bigint"),
+        _ => None,
+    }
+}
+
+/// Lists active tokens with the highest community counter
+///
+/// Counters are snapshots taken by the metadata sync and re-fetched by its
+/// refresh every `METADATA_REFRESH_DAYS`, so this ranks community size as
+/// of each token's `refreshed_at`, not short-term momentum.
+///
+/// # Query Parameters
+/// See [`TrendingParams`], e.g. `/metadata/trending?sort=twitter_followers&limit=20`.
+///
+/// # Returns
+/// JSON array of [`TrendingToken`], highest count first (tokens without the
+/// counter are left out); 400 on an unknown `sort`
+pub async fn trending_tokens(
+    State(config): State<Arc<RwLock<Config>>>,
+    Query(params): Query<TrendingParams>,
+) -> ApiResult<Vec<TrendingToken>> {
+    let Some(expr) = community_sort_expr(params.sort.as_deref()) else {
+        return Err(api_error(
+            StatusCode::BAD_REQUEST,
+            format!("Invalid sort: expected one of {}", COMMUNITY_SORT_KEYS.join(", ")),
+        ));
+    };
+    let limit = params.limit.unwrap_or(DEFAULT_TRENDING_LIMIT).clamp(1, MAX_LIMIT);
+
+    let pool = config.read().await.postgres_db.read_pool().clone();
+
+    let mut qb = QueryBuilder::<Postgres>::new("SELECT m.chainid, m.address, m.tokenid, m.symbol, m.name, m.image, ");
+    qb.push(expr)
+        .push(" AS count, m.community_data, COALESCE(m.refreshed_at, m.updated_at, m.created_at) AS refreshed_at")
+        .push(" FROM metadata m WHERE m.is_active AND ")
+        .push(expr)
+        .push(" IS NOT NULL ORDER BY ")
+        .push(expr)
+        .push(" DESC, m.id LIMIT ")
+        .push_bind(limit);
+
+    let items = qb
+        .build_query_as::<TrendingToken>()
+        .fetch_all(&pool)
+        .await
+        .map_err(internal_error)?;
+
+    Ok(Json(items))
+}
+
+// ======================= Holder Count =======================
+
+/// Query parameters of `GET /metadata/top-holders-count`
+#[derive(Debug, Default, Deserialize)]
+pub struct TopHoldersParams {
+    /// Only tokens on this chain
+    pub chain_id: Option<i64>,
+    /// Page size (default 50, capped at 200)
+    pub limit: Option<i64>,
+}
+
+/// One token of the holder count ranking
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct HolderCountItem {
+    /// Chain ID
+    pub chainid: i64,
+    /// Contract address (lowercase hex)
+    pub address: String,
+    /// CoinGecko token ID
+    pub tokenid: Option<String>,
+    /// Token symbol
+    pub symbol: String,
+    /// Token name
+    pub name: String,
+    /// Logo URL
+    pub image: Option<String>,
+    /// Token holders according to Blockscout
+    pub holder_count: i64,
+}
+
+/// Lists active tokens with the most holders
+///
+/// # Query Parameters
+/// See [`TopHoldersParams`], e.g. `/metadata/top-holders-count?chain_id=1&limit=50`.
+///
+/// # Returns
+/// JSON array of [`HolderCountItem`], most holders first (tokens without a
+/// holder count are left out)
+pub async fn top_holders_count(
+    State(config): State<Arc<RwLock<Config>>>,
+    Query(params): Query<TopHoldersParams>,
+) -> ApiResult<Vec<HolderCountItem>> {
+    let pool = config.read().await.postgres_db.read_pool().clone();
+
+    let items = sqlx::query_as::<_, HolderCountItem>(
+        r#"
+        SELECT chainid, address, tokenid, symbol, name, image, holder_count
+        FROM metadata
+        WHERE is_active AND holder_count IS NOT NULL AND ($1::BIGINT IS NULL OR chainid = $1)
+        ORDER BY holder_count DESC, id
+        LIMIT $2
+        "#,
+    )
+    .bind(params.chain_id)
+    .bind(clamp_limit(params.limit))
+    .fetch_all(&pool)
+    .await
+    .map_err(internal_error)?;
+
+    Ok(Json(items))
+}
+
+// ======================= NFTs =======================
+
+/// Query parameters of `GET /nfts`
+#[derive(Debug, Default, Deserialize)]
+pub struct NftListParams {
+    /// Only collections on this chain
+    pub chainid: Option<i64>,
+    /// Page size (default 50, capped at 200)
+    pub limit: Option<i64>,
+    /// Rows to skip
+    pub offset: Option<i64>,
+}
+
+/// One row of the NFT collection catalog
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct NftListItem {
+    /// Chain ID
+    pub chainid: i64,
+    /// Contract address (lowercase hex)
+    pub address: String,
+    /// CoinGecko NFT collection ID
+    pub nftid: String,
+    /// Collection symbol
+    pub symbol: String,
+    /// Collection name
+    pub name: String,
+    /// Logo URL
+    pub image: Option<String>,
+    /// Token standard (ERC-721, ERC-1155, ...)
+    pub token_type: Option<String>,
+    /// Contract verification status
+    pub is_verified: Option<bool>,
+    /// Floor price in USD
+    pub floor_price_usd: Option<f64>,
+    /// 24h trading volume in USD
+    pub volume_24h_usd: Option<f64>,
+    /// Market cap in USD
+    pub market_cap_usd: Option<f64>,
+}
+
+/// Full metadata of one NFT collection
+#[derive(Debug, Serialize, sqlx::FromRow)]
+pub struct NftDetail {
+    /// Database row ID
+    #[serde(skip)]
+    pub id: i32,
+    /// Chain ID
+    pub chainid: i64,
+    /// Contract address (lowercase hex)
+    pub address: String,
+    /// CoinGecko NFT collection ID
+    pub nftid: String,
+    /// Token standard (ERC-721, ERC-1155, ...)
+    pub token_type: Option<String>,
+    /// Collection symbol
+    pub symbol: String,
+    /// Collection name
+    pub name: String,
+    /// Project homepage
+    pub homepage: Option<String>,
+    /// Logo URL
+    pub image: Option<String>,
+    /// Collection description
+    pub description: Option<String>,
+    /// Social/contact links
+    pub social_links: Option<sqlx::types::Json<Value>>,
+    /// Contract verification status
+    pub is_verified: Option<bool>,
+    /// Risk bucket (low/medium/high)
+    pub risk_level: Option<String>,
+    /// Floor price in USD
+    pub floor_price_usd: Option<f64>,
+    /// 24h trading volume in USD
+    pub volume_24h_usd: Option<f64>,
+    /// Market cap in USD
+    pub market_cap_usd: Option<f64>,
+    /// False once the collection is gone from CoinGecko
+    pub is_active: bool,
+    /// When the row was created
+    pub created_at: NaiveDateTime,
+    /// When the row was last updated
+    pub updated_at: Option<NaiveDateTime>,
+}
+
+/// Lists NFT collections by market cap, with pagination
+///
+/// # Query Parameters
+/// See [`NftListParams`], e.g. `/nfts?chainid=1&limit=50&offset=0`.
+/// Deactivated collections are hidden.
+///
+/// # Returns
+/// `{"items": [...], "limit": n, "offset": n}`
+pub async fn list_nfts(
+    State(config): State<Arc<RwLock<Config>>>,
+    Query(params): Query<NftListParams>,
+) -> ApiResult<Value> {
+    let pool = config.read().await.postgres_db.read_pool().clone();
+    let limit = clamp_limit(params.limit);
+    let offset = params.offset.unwrap_or(0).max(0);
+
+    let items = sqlx::query_as::<_, NftListItem>(
+        r#"
+        SELECT
+            m.chainid, m.address, m.nftid, m.symbol, m.name, m.image, m.token_type, m.is_verified,
+            n.floor_price_usd::FLOAT8 AS floor_price_usd,
+            n.volume_24h_usd::FLOAT8 AS volume_24h_usd,
+            n.market_cap_usd::FLOAT8 AS market_cap_usd
+        FROM metadata m
+        LEFT JOIN nft_market_data n ON n.nftid = m.nftid
+        WHERE m.nftid IS NOT NULL AND m.is_active AND ($1::BIGINT IS NULL OR m.chainid = $1)
+        ORDER BY n.market_cap_usd DESC NULLS LAST, m.id
+        LIMIT $2 OFFSET $3
+        "#,
+    )
+    .bind(params.chainid)
+    .bind(limit)
+    .bind(offset)
+    .fetch_all(&pool)
+    .await
+    .map_err(internal_error)?;
+
+    Ok(Json(json!({"items": items, "limit": limit, "offset": offset})))
+}
+
+/// Returns the metadata of one NFT collection
+///
+/// # Path Parameters
+/// * `chainid` - Chain ID
+/// * `address` - Contract address (case-insensitive)
+///
+/// # Returns
+/// JSON [`NftDetail`] with a weak `ETag`; 304 if `If-None-Match` matches,
+/// 404 if no NFT collection is stored for the contract
+pub async fn nft_detail(
+    State(config): State<Arc<RwLock<Config>>>,
+    Path((chainid, address)): Path<(i64, String)>,
+    headers: HeaderMap,
+) -> Result<Response, (StatusCode, Json<Value>)> {
+    let pool = config.read().await.postgres_db.read_pool().clone();
+
+    let nft = sqlx::query_as::<_, NftDetail>(
+        r#"
+        SELECT
+            m.id, m.chainid, m.address, m.nftid, m.token_type, m.symbol, m.name, m.homepage, m.image,
+            m.description, m.social_links, m.is_verified, m.risk_level,
+            n.floor_price_usd::FLOAT8 AS floor_price_usd,
+            n.volume_24h_usd::FLOAT8 AS volume_24h_usd,
+            n.market_cap_usd::FLOAT8 AS market_cap_usd,
+            m.is_active, m.created_at, m.updated_at
+        FROM metadata m
+        LEFT JOIN nft_market_data n ON n.nftid = m.nftid
+        WHERE m.chainid = $1 AND m.address = $2 AND m.nftid IS NOT NULL
+        "#,
+    )
+    .bind(chainid)
+    .bind(address.to_lowercase())
+    .fetch_optional(&pool)
+    .await
+    .map_err(internal_error)?
+    .ok_or_else(|| api_error(StatusCode::NOT_FOUND, "NFT collection not found"))?;
+
+    // Market data is refreshed independently of the metadata row
+    let etag = weak_etag((
+        nft.id,
+        nft.updated_at.unwrap_or(nft.created_at),
+        nft.floor_price_usd.map(f64::to_bits),
+        nft.volume_24h_usd.map(f64::to_bits),
+        nft.market_cap_usd.map(f64::to_bits),
+    ));
+    Ok(json_with_etag(&headers, &etag, nft))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_api_error_body() {
+        let (status, Json(body)) = api_error(StatusCode::NOT_FOUND, "Token not found");
+        assert_eq!(status, StatusCode::NOT_FOUND);
+        assert_eq!(body, json!({"error": "Token not found"}));
+    }
+
+    #[test]
+    fn test_metadata_change_serialization() {
+        let change = MetadataChange {
+            field: "symbol".to_string(),
+            old_value: Some("OLD".to_string()),
+            new_value: Some("NEW".to_string()),
+            changed_at: DateTime::from_timestamp(1_700_000_000, 0).unwrap(),
+        };
+
+        let value = serde_json::to_value(&change).unwrap();
+        assert_eq!(value["field"], "symbol");
+        assert_eq!(value["old_value"], "OLD");
+        assert_eq!(value["new_value"], "NEW");
+        assert_eq!(value["changed_at"], "2023-11-14T22:13:20Z");
+    }
+
+    #[test]
+    fn test_order_clause_whitelist() {
+        assert_eq!(order_clause(None), order_clause(Some("market_cap")));
+        assert!(order_clause(Some("symbol")).is_some());
+        assert!(order_clause(Some("market_cap; DROP TABLE metadata")).is_none());
+    }
+
+    #[test]
+    fn test_community_sort_expr() {
+        assert_eq!(community_sort_expr(None), community_sort_expr(Some("twitter_followers")));
+        for key in COMMUNITY_SORT_KEYS {
+            assert!(community_sort_expr(Some(key)).unwrap().contains(key));
+        }
+        assert!(community_sort_expr(Some("twitter_followers')::bigint; --")).is_none());
+    }
+
+    #[test]
+    fn test_clamp_limit() {
+        assert_eq!(clamp_limit(None), DEFAULT_LIMIT);
+        assert_eq!(clamp_limit(Some(1000)), MAX_LIMIT);
+        assert_eq!(clamp_limit(Some(0)), 1);
+        assert_eq!(clamp_limit(Some(10)), 10);
+    }
+
+    #[test]
+    fn test_escape_like() {
+        assert_eq!(escape_like("usd"), "usd");
+        assert_eq!(escape_like("50%_off\\"), "50\\%\\_off\\\\");
+    }
+
+    #[test]
+    fn test_build_token_list_query_binds_filters() {
+        let params = TokenListParams {
+            chainid: Some(1),
+            verified: Some(true),
+            q: Some("usd".to_string()),
+            ..Default::default()
+        };
+        let qb = build_token_list_query(&params, order_clause(None).unwrap());
+        let sql = qb.sql();
+
+        assert!(sql.contains("m.chainid = $1"));
+        assert!(sql.contains("m.is_verified = $2"));
+        assert!(sql.contains("m.symbol ILIKE $3 OR m.name ILIKE $4"));
+        assert!(sql.contains("ORDER BY md.market_cap DESC NULLS LAST"));
+        assert!(sql.contains("LIMIT $5 OFFSET $6"));
+        assert!(!sql.contains("usd"));
+    }
+
+    #[test]
+    fn test_build_token_list_query_without_filters() {
+        let params = TokenListParams::default();
+        let qb = build_token_list_query(&params, order_clause(Some("name")).unwrap());
+        let sql = qb.sql();
+
+        assert!(!sql.contains(" AND "));
+        assert!(sql.contains("WHERE m.is_active ORDER BY m.name, m.id LIMIT $1 OFFSET $2"));
+    }
+
+    #[test]
+    fn test_build_token_list_query_min_age_days() {
+        let params = TokenListParams {
+            min_age_days: Some(365),
+            ..Default::default()
+        };
+        let qb = build_token_list_query(&params, order_clause(None).unwrap());
+        assert!(qb.sql().contains("(m.genesis_date IS NULL OR m.genesis_date < NOW() - make_interval(days => $1))"));
+    }
+
+    #[test]
+    fn test_build_token_list_query_include_inactive() {
+        let params = TokenListParams {
+            include_inactive: Some(true),
+            ..Default::default()
+        };
+        let qb = build_token_list_query(&params, order_clause(None).unwrap());
+        assert!(!qb.sql().contains("is_active"));
+    }
+
+    #[test]
+    fn test_prefix_tsquery() {
+        assert_eq!(prefix_tsquery("USDC").as_deref(), Some("usdc:*"));
+        assert_eq!(prefix_tsquery("wrapped  eth").as_deref(), Some("wrapped:* & eth:*"));
+        assert_eq!(prefix_tsquery("a&b|!c:*").as_deref(), Some("a:* & b:* & c:*"));
+        assert!(prefix_tsquery("&|!()").is_none());
+    }
+
+    #[tokio::test]
+    async fn test_cors_preflight() {
+        use axum::body::Body;
+        use axum::http::Request;
+        use tower::ServiceExt;
+
+        let app = Router::new()
+            .route("/tokens", get(|| async { "[]" }))
+            .layer(cors_layer(&["https://app.example.com".to_string()]));
+
+        let preflight = Request::builder()
+            .method(Method::OPTIONS)
+            .uri("/tokens")
+            .header(header::ORIGIN, "https://app.example.com")
+            .header(header::ACCESS_CONTROL_REQUEST_METHOD, "GET")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.clone().oneshot(preflight).await.unwrap();
+        assert_eq!(
+            resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).unwrap(),
+            "https://app.example.com"
+        );
+
+        let foreign = Request::builder()
+            .uri("/tokens")
+            .header(header::ORIGIN, "https://evil.example.com")
+            .body(Body::empty())
+            .unwrap();
+        let resp = app.oneshot(foreign).await.unwrap();
+        assert!(resp.headers().get(header::ACCESS_CONTROL_ALLOW_ORIGIN).is_none());
+    }
+
+    #[test]
+    fn test_weak_etag_format_and_stability() {
+        let a = weak_etag((1, "2025-01-01"));
+        assert!(a.starts_with("W/\"") && a.ends_with('"'));
+        assert_eq!(a, weak_etag((1, "2025-01-01")));
+        assert_ne!(a, weak_etag((1, "2025-01-02")));
+    }
+
+    #[test]
+    fn test_etag_matches() {
+        let etag = weak_etag(42);
+        let mut headers = HeaderMap::new();
+        assert!(!etag_matches(&headers, &etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&etag).unwrap());
+        assert!(etag_matches(&headers, &etag));
+
+        // Weak comparison: strong form of the same tag also matches
+        let strong = etag.trim_start_matches("W/").to_string();
+        let list = format!("\"other\", {}", strong);
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&list).unwrap());
+        assert!(etag_matches(&headers, &etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("*"));
+        assert!(etag_matches(&headers, &etag));
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_static("\"other\""));
+        assert!(!etag_matches(&headers, &etag));
+    }
+
+    #[test]
+    fn test_json_with_etag_not_modified() {
+        let etag = weak_etag("v1");
+        let mut headers = HeaderMap::new();
+
+        let resp = json_with_etag(&headers, &etag, json!({"a": 1}));
+        assert_eq!(resp.status(), StatusCode::OK);
+        assert_eq!(resp.headers().get(header::ETAG).unwrap(), etag.as_str());
+
+        headers.insert(header::IF_NONE_MATCH, HeaderValue::from_str(&etag).unwrap());
+        let resp = json_with_etag(&headers, &etag, json!({"a": 1}));
+        assert_eq!(resp.status(), StatusCode::NOT_MODIFIED);
+        assert_eq!(resp.headers().get(header::ETAG).unwrap(), etag.as_str());
+    }
+}
@@ -0,0 +1,130 @@
+//! Configuration check mode (`--check-config`)
+//!
+//! Validates the deployment environment without starting the HTTPS server
+//! or the background tasks: loads the configuration, probes the databases
+//! and verifies the CoinGecko key, then prints a pass/fail report.
+
+use anyhow::{Context, Result};
+
+use crate::config::Config;
+
+/// CLI flag selecting the configuration check mode
+pub const CHECK_CONFIG_FLAG: &str = "--check-config";
+
+/// Returns whether the process arguments request the configuration check
+pub fn is_check_requested(mut args: impl Iterator<Item = String>) -> bool {
+    args.any(|arg| arg == CHECK_CONFIG_FLAG)
+}
+
+/// Runs every check and prints the report to stdout
+///
+/// # Checks
+/// - `environment` - `Config::from_env` succeeds (required variables, URLs, addresses)
+/// - `database` - the primary is reachable and accepts writes
+/// - `replica` - `REPLICA_DATABASE_URL` (if set) is reachable and a replica
+/// - `coingecko` - `/api/v3/ping` accepts `COINGECKO_KEY`
+/// - `eth_rpc` - `ETH_RPC_URL` (if set) answers `eth_blockNumber`
+/// - `dex_rpc` - `DEX_RPC_URL` (if set) answers `eth_blockNumber`
+///
+/// Checks after `environment` are skipped when it fails.
+///
+/// # Returns
+/// `true` if every check passed
+pub async fn run_config_check() -> bool {
+    println!("Configuration check");
+
+    let config = match Config::from_env() {
+        Ok(config) => config,
+        Err(e) => {
+            println!("{}", format_check("environment", &Err(e)));
+            println!("Result: FAIL");
+            return false;
+        }
+    };
+
+    let results = [
+        ("environment", Ok("loaded".to_string())),
+        (
+            "database",
+            config
+                .postgres_db
+                .health_check()
+                .await
+                .map(|_| "primary reachable and writable".to_string()),
+        ),
+        ("replica", check_replica(&config).await),
+        ("coingecko", ping_coingecko(&config).await.map(|_| "API key accepted".to_string())),
+        ("eth_rpc", check_rpc("ETH_RPC_URL", config.eth_rpc_url.as_deref()).await),
+        ("dex_rpc", check_rpc("DEX_RPC_URL", config.dex_rpc_url.as_deref()).await),
+    ];
+
+    let mut passed = true;
+    for (name, result) in &results {
+        println!("{}", format_check(name, result));
+        passed &= result.is_ok();
+    }
+    println!("Result: {}", if passed { "PASS" } else { "FAIL" });
+    passed
+}
+
+/// Verifies the read replica, reporting when none is configured
+async fn check_replica(config: &Config) -> Result<String> {
+    if config.postgres_db.replica_pool.is_none() {
+        return Ok("not configured".to_string());
+    }
+    config.postgres_db.verify_replica().await?;
+    Ok("reachable and in recovery".to_string())
+}
+
+/// Calls CoinGecko's `/api/v3/ping` with the configured key
+async fn ping_coingecko(config: &Config) -> Result<()> {
+    config
+        .http_get(&format!("{}/api/v3/ping", config.coingecko_base_url))
+        .header("x-cg-demo-api-key", &config.coingecko_key)
+        .send()
+        .await
+        .context("CoinGecko unreachable")?
+        .error_for_status()
+        .context("CoinGecko rejected the request")?;
+    Ok(())
+}
+
+/// Fetches the head block from the JSON-RPC endpoint in `var`, reporting when none is configured
+async fn check_rpc(var: &str, url: Option<&str>) -> Result<String> {
+    use alloy::providers::{Provider, ProviderBuilder};
+
+    let Some(url) = url else {
+        return Ok("not configured".to_string());
+    };
+    let provider = ProviderBuilder::new().connect_http(url.parse().with_context(|| format!("Invalid {}", var))?);
+    let head = provider.get_block_number().await.context("eth_blockNumber failed")?;
+    Ok(format!("head block {}", head))
+}
+
+/// Formats one line of the report
+fn format_check(name: &str, result: &Result<String>) -> String {
+    match result {
+        Ok(detail) => format!("  ✅ {}: {}", name, detail),
+        Err(e) => format!("  ❌ {}: {:#}", name, e),
+    }
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_is_check_requested() {
+        let args = |list: &[&str]| list.iter().map(|s| s.to_string()).collect::<Vec<_>>().into_iter();
+        assert!(is_check_requested(args(&["indexer", "--check-config"])));
+        assert!(!is_check_requested(args(&["indexer"])));
+        assert!(!is_check_requested(args(&["indexer", "--check"])));
+    }
+
+    #[test]
+    fn test_format_check() {
+        assert_eq!(format_check("database", &Ok("ok".to_string())), "  ✅ database: ok");
+        let err = Err(anyhow::anyhow!("refused").context("connect failed"));
+        assert_eq!(format_check("database", &err), "  ❌ database: connect failed: refused");
+    }
+}
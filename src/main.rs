@@ -3,7 +3,7 @@
 //! This is the main entry point for the blockchain indexer service.
 //! The service provides:
 //! - Background data synchronization tasks (metadata, market data, forex rates)
-//! - HTTPS API server for health checks and management operations
+//! - HTTPS API server for health checks, read queries and management operations
 //! - PostgreSQL database persistence
 //! - Distributed logging via Loki
 //!
@@ -13,25 +13,29 @@
 //! - Background task: Runs all data synchronization tasks in parallel
 //!
 //! # Configuration
-//! All configuration is loaded from environment variables (see .env file)
+//! All configuration is loaded from environment variables (see .env file).
+//! Run with `--check-config` to validate it without starting the service.
 //!
 //! # Startup Sequence
 //! 1. Load environment variables
 //! 2. Initialize cryptographic provider (Rustls)
 //! 3. Setup distributed logging (Loki)
-//! 4. Initialize database and run migrations
+//! 4. Wait for the database, then run migrations
 //! 5. Start background synchronization tasks
-//! 6. Start HTTPS API server
+//! 6. Start HTTPS API server (plain HTTP with `TLS_ENABLED=false`, for use
+//!    behind a TLS-terminating proxy)
 
 use anyhow::{Result, Context, anyhow};
-use axum::{Router, routing::{get, post}};
+use axum::{Json, Router, extract::State, middleware, routing::{get, post}};
 use axum_server::tls_rustls::RustlsConfig;
-use std::{env, net::SocketAddr, process, sync::Arc};
+use std::{env, net::SocketAddr, process, sync::Arc, time::{Duration, SystemTime}};
 use tokio::sync::RwLock;
 use tracing::{error, info, warn};
 use tracing_loki::url::Url;
-use tracing_subscriber::{layer::SubscriberExt, util::SubscriberInitExt};
+use tracing_subscriber::{EnvFilter, layer::SubscriberExt, util::SubscriberInitExt};
 
+mod api;
+mod check;
 mod config;
 mod manage;
 mod worker;
@@ -39,7 +43,7 @@ mod tasks;
 mod utils;
 
 use config::Config;
-use manage::manager_rpc;
+use manage::{audit_log, export_metadata_csv, manager_rate_limit, manager_rpc, watchlist};
 use tasks::start_all_tasks;
 
 // ======================= Constants =======================
@@ -50,6 +54,15 @@ const DEFAULT_SERVER_ADDR: &str = "0.0.0.0:8443";
 /// Default Loki server URL for log aggregation
 const DEFAULT_LOKI_URL: &str = "http://127.0.0.1:3100";
 
+/// Log filter used when `RUST_LOG` is unset or invalid
+const DEFAULT_LOG_FILTER: &str = "info";
+
+/// Default time to wait for the database at startup (seconds)
+const DEFAULT_DB_STARTUP_TIMEOUT_SECS: u64 = 60;
+
+/// Interval between TLS certificate file checks when `TLS_RELOAD=true`
+const TLS_RELOAD_CHECK_INTERVAL: Duration = Duration::from_secs(60);
+
 // ======================= Helper Functions =======================
 
 /// Health check endpoint handler
@@ -66,56 +79,127 @@ async fn health_check() -> &'static str {
     "OK"
 }
 
+/// Service status endpoint handler
+///
+/// Reports internal runtime state that is useful for operators, such as
+/// the circuit breakers and rate limiters guarding external APIs.
+///
+/// # Returns
+/// JSON object, e.g.
+/// `{"circuit_breakers": {"coingecko": {"state": "closed", ...}},
+/// "rate_limiters": {"coingecko": {"utilization": 0.25, ...}}, "pool_stats": {"size": 2, ...},
+/// "replica_pool_stats": null}` (`replica_pool_stats` is null without a replica)
+async fn status(State(config): State<Arc<RwLock<Config>>>) -> Json<serde_json::Value> {
+    let cfg = config.read().await;
+    Json(serde_json::json!({
+        "circuit_breakers": {
+            "coingecko": cfg.coingecko_breaker.snapshot(),
+        },
+        "rate_limiters": {
+            "coingecko": cfg.coingecko_rate_limiter.snapshot(),
+        },
+        "pool_stats": cfg.postgres_db.pool_stats(),
+        "replica_pool_stats": cfg.postgres_db.replica_pool_stats(),
+    }))
+}
+
+/// Database pool statistics endpoint handler (development only)
+///
+/// Only routed when `DEBUG_ENDPOINTS=true`.
+///
+/// # Returns
+/// JSON [`config::PoolStats`], e.g. `{"size": 2, "idle": 1, "active": 1, "max_connections": 5}`
+async fn debug_pool_stats(State(config): State<Arc<RwLock<Config>>>) -> Json<config::PoolStats> {
+    Json(config.read().await.postgres_db.pool_stats())
+}
+
 /// Initializes distributed logging with Loki integration
 ///
 /// Sets up a dual logging pipeline:
 /// 1. **Console output**: Formatted logs to stdout/stderr
 /// 2. **Loki integration**: Structured logs sent to Loki for aggregation
 ///
+/// # Log Filtering
+/// Both outputs share one `EnvFilter` read from `RUST_LOG` (default `info`,
+/// also used when `RUST_LOG` does not parse). Targets are module paths of
+/// the `indexer` crate, e.g.:
+/// - `RUST_LOG=debug` - everything at debug
+/// - `RUST_LOG=info,indexer::utils=warn` - quiet the HTTP retry/rate limiter logs
+/// - `RUST_LOG=info,indexer::worker::metadata=debug` - debug a single worker
+/// - `RUST_LOG=info,sqlx=warn` - hide sqlx statement logs
+///
 /// # Loki Configuration
 /// - URL: From `LOKI_URL` env var or default (http://127.0.0.1:3100)
 /// - Service label: "indexer"
 /// - Extra fields: Process ID (pid)
+/// - Span fields: every line also carries the fields of its enclosing spans,
+///   e.g. `pipeline`/`run_id` of a sync run and `url`/`attempt` of an HTTP
+///   request, so one run can be filtered with `| json | run_id="..."`
+///   (not labels: a per-run label would explode stream cardinality)
 ///
 /// # Returns
 /// * `Ok(())` - Logging configured successfully
-/// * `Err` - Failed to setup Loki (console logging still works)
+/// * `Err` - Failed to setup Loki (console logging is still installed)
 ///
 /// # Note
 /// This function spawns a background task to send logs to Loki.
 /// If Loki is unavailable, logs will only go to stdout.
 async fn setup_tracing() -> Result<()> {
+    let filter = EnvFilter::try_from_default_env().unwrap_or_else(|_| EnvFilter::new(DEFAULT_LOG_FILTER));
+
     // Get Loki URL from environment or use default
     let loki_url_str = env::var("LOKI_URL")
         .unwrap_or_else(|_| DEFAULT_LOKI_URL.to_string());
-    
-    let loki_url = Url::parse(&loki_url_str)
-        .context(format!("Invalid Loki URL: {}", loki_url_str))?;
 
-    // Build Loki layer with metadata
-    let (layer, task) = tracing_loki::builder()
-        .label("service", "indexer")?  // Service identifier for Loki queries
-        .extra_field("pid", format!("{}", process::id()))?  // Process ID for debugging
-        .build_url(loki_url)?;
+    let loki = build_loki_layer(&loki_url_str);
 
-    // Initialize tracing subscriber with dual output
+    // Initialize tracing subscriber with dual output; console logging is
+    // installed even when the Loki layer could not be built
+    let (loki_layer, loki_result) = match loki {
+        Ok((layer, task)) => (Some(layer), Ok(task)),
+        Err(e) => (None, Err(e)),
+    };
     tracing_subscriber::registry()
+        .with(filter)  // Shared level/module filter (RUST_LOG)
         .with(tracing_subscriber::fmt::layer())  // Console output (stdout)
-        .with(layer)  // Loki integration
+        .with(loki_layer)  // Loki integration
         .init();
 
     // Spawn background task to send logs to Loki
-    tokio::spawn(task);
-    
+    tokio::spawn(loki_result?);
+
     info!("Loki integration enabled at {}", loki_url_str);
     Ok(())
 }
 
-/// Loads TLS configuration from PEM files
+/// Builds the Loki layer and its background sender task
+fn build_loki_layer(loki_url_str: &str) -> Result<(tracing_loki::Layer, tracing_loki::BackgroundTask)> {
+    let loki_url = Url::parse(loki_url_str)
+        .context(format!("Invalid Loki URL: {}", loki_url_str))?;
+
+    // Build Loki layer with metadata
+    let layer = tracing_loki::builder()
+        .label("service", "indexer")?  // Service identifier for Loki queries
+        .extra_field("pid", format!("{}", process::id()))?  // Process ID for debugging
+        .build_url(loki_url)?;
+    Ok(layer)
+}
+
+/// Returns the TLS certificate and private key paths
 ///
-/// Reads TLS certificate and private key from files specified in environment variables:
 /// - `TLS_CERT_PATH`: Path to certificate file (default: ./cert.pem)
 /// - `TLS_KEY_PATH`: Path to private key file (default: ./key.pem)
+fn tls_paths() -> (String, String) {
+    let cert_path = env::var("TLS_CERT_PATH")
+        .unwrap_or_else(|_| "./cert.pem".to_string());
+    let key_path = env::var("TLS_KEY_PATH")
+        .unwrap_or_else(|_| "./key.pem".to_string());
+    (cert_path, key_path)
+}
+
+/// Loads TLS configuration from PEM files
+///
+/// Reads TLS certificate and private key from the files returned by [`tls_paths`].
 ///
 /// # Returns
 /// * `Ok(RustlsConfig)` - TLS configuration ready for use
@@ -126,10 +210,7 @@ async fn setup_tracing() -> Result<()> {
 /// - Certificate should be valid and not expired
 /// - Self-signed certificates work for development but not recommended for production
 async fn load_tls_config() -> Result<RustlsConfig> {
-    let cert_path = env::var("TLS_CERT_PATH")
-        .unwrap_or_else(|_| "./cert.pem".to_string());
-    let key_path = env::var("TLS_KEY_PATH")
-        .unwrap_or_else(|_| "./key.pem".to_string());
+    let (cert_path, key_path) = tls_paths();
 
     RustlsConfig::from_pem_file(&cert_path, &key_path)
         .await
@@ -139,6 +220,49 @@ async fn load_tls_config() -> Result<RustlsConfig> {
         ))
 }
 
+/// Returns the modification times of the certificate and key files
+///
+/// A file that cannot be read yields `None`, so a missing file also counts
+/// as a change once it reappears.
+fn tls_file_mtimes(cert_path: &str, key_path: &str) -> (Option<SystemTime>, Option<SystemTime>) {
+    let mtime = |path: &str| std::fs::metadata(path).and_then(|m| m.modified()).ok();
+    (mtime(cert_path), mtime(key_path))
+}
+
+/// Reloads the TLS certificate whenever the PEM files change (`TLS_RELOAD=true`)
+///
+/// Polls the files' modification times every `TLS_RELOAD_CHECK_INTERVAL`
+/// and calls `RustlsConfig::reload_from_pem_file` on change, so a renewed
+/// certificate (e.g. Let's Encrypt) is picked up without a restart. New
+/// connections use the new certificate; established ones are unaffected.
+///
+/// If the new files are invalid (e.g. the key is not written yet) the old
+/// certificate keeps being served and the reload is retried next check.
+async fn watch_tls_files(tls_config: RustlsConfig) {
+    let (cert_path, key_path) = tls_paths();
+    let mut loaded = tls_file_mtimes(&cert_path, &key_path);
+    info!("🔐 TLS hot-reload enabled, watching {} and {}", cert_path, key_path);
+
+    loop {
+        tokio::time::sleep(TLS_RELOAD_CHECK_INTERVAL).await;
+
+        let current = tls_file_mtimes(&cert_path, &key_path);
+        if current == loaded {
+            continue;
+        }
+
+        match tls_config.reload_from_pem_file(&cert_path, &key_path).await {
+            Ok(()) => {
+                loaded = current;
+                info!("🔐 TLS certificate reloaded from {}", cert_path);
+            }
+            Err(e) => {
+                warn!("⚠️ TLS reload failed, keeping the current certificate: {}", e);
+            }
+        }
+    }
+}
+
 // ======================= Main Entry Point =======================
 
 // ======================= Main Entry Point =======================
@@ -150,7 +274,7 @@ async fn load_tls_config() -> Result<RustlsConfig> {
 /// 2. Distributed logging configuration
 /// 3. Database initialization and migrations
 /// 4. Background task spawning
-/// 5. HTTPS server startup
+/// 5. HTTPS server startup (or plain HTTP with `TLS_ENABLED=false`)
 ///
 /// # Returns
 /// * `Ok(())` - Server shut down gracefully (never happens under normal operation)
@@ -163,9 +287,9 @@ async fn load_tls_config() -> Result<RustlsConfig> {
 /// - Database connection fails
 #[tokio::main]
 async fn main() -> Result<()> {
-    // Load environment variables from .env file (if exists)
-    // This is optional - production deployments may use system env vars
-    dotenvy::dotenv().ok();
+    // Load .env and .env.{ENVIRONMENT} (if they exist) so LOKI_URL and
+    // RUST_LOG are set before tracing starts; production may use system env vars
+    config::load_env();
 
     // Step 1: Initialize Rustls cryptographic provider
     // Must be done before any TLS operations
@@ -173,6 +297,13 @@ async fn main() -> Result<()> {
         .install_default()
         .map_err(|_| anyhow!("Failed to install ring crypto provider"))?;
 
+    // `--check-config`: validate the environment, print a report and exit
+    // (no logging pipeline, server or background tasks)
+    if check::is_check_requested(env::args().skip(1)) {
+        let passed = check::run_config_check().await;
+        process::exit(if passed { 0 } else { 1 });
+    }
+
     // Step 2: Initialize distributed logging (Loki + stdout)
     // Non-fatal error - service continues even if Loki is unavailable
     if let Err(e) = setup_tracing().await {
@@ -183,15 +314,52 @@ async fn main() -> Result<()> {
     }
 
     // Step 3: Load configuration and initialize database
-    let config = Arc::new(RwLock::new(Config::from_env()));
+    let config = match Config::from_env() {
+        Ok(config) => {
+            info!("🌍 Environment: {}", config.environment());
+            info!(
+                "🔒 Database sslmode: {} (CA certificate: {})",
+                config.postgres_ssl_mode,
+                config.postgres_ssl_ca_cert.as_deref().unwrap_or("system roots")
+            );
+            Arc::new(RwLock::new(config))
+        }
+        Err(e) => {
+            // Configuration errors are operator mistakes: report cleanly, no backtrace
+            error!("❌ Invalid configuration: {:#}", e);
+            process::exit(1);
+        }
+    };
     {
         let cfg = config.read().await;
+
+        // Wait for the database (it may still be starting, e.g. in Docker Compose)
+        let db_startup_timeout_secs = env::var("DB_STARTUP_TIMEOUT_SECS")
+            .ok()
+            .and_then(|v| v.parse().ok())
+            .unwrap_or(DEFAULT_DB_STARTUP_TIMEOUT_SECS);
+        cfg.postgres_db
+            .wait_until_ready(Duration::from_secs(db_startup_timeout_secs))
+            .await
+            .context("Database did not become ready")?;
+
+        // Make sure REPLICA_DATABASE_URL (if set) really is a replica
+        cfg.postgres_db
+            .verify_replica()
+            .await
+            .context("Read replica verification failed")?;
         
         // Run database migrations (embedded at compile time)
         cfg.postgres_db
             .init_database()
             .await
             .context("Failed to run database migrations")?;
+
+        // Fail fast if a column the workers depend on is missing
+        cfg.postgres_db
+            .verify_schema()
+            .await
+            .context("Database schema verification failed")?;
         
         // Initialize chains table with default blockchain networks
         cfg.postgres_db
@@ -215,10 +383,35 @@ async fn main() -> Result<()> {
     });
 
     // Step 5: Build and configure HTTP router
-    let app = Router::new()
+    let (allowed_origins, debug_endpoints) = {
+        let cfg = config.read().await;
+        (cfg.allowed_origins.clone(), cfg.debug_endpoints)
+    };
+    let mut app = Router::new()
         .route("/health", get(health_check))
-        .route("/manager", post(manager_rpc))
-        .with_state(config);
+        .route("/status", get(status))
+        .route(
+            "/manager",
+            post(manager_rpc)
+                .route_layer(middleware::from_fn_with_state(config.clone(), manager_rate_limit)),
+        )
+        .route(
+            "/audit",
+            get(audit_log)
+                .route_layer(middleware::from_fn_with_state(config.clone(), manager_rate_limit)),
+        )
+        .route(
+            "/export/metadata.csv",
+            get(export_metadata_csv)
+                .route_layer(middleware::from_fn_with_state(config.clone(), manager_rate_limit)),
+        )
+        .route("/watchlist", get(watchlist))
+        .merge(api::router(&allowed_origins));
+    if debug_endpoints {
+        warn!("⚠️ DEBUG_ENDPOINTS enabled, serving /debug/* routes");
+        app = app.route("/debug/pool_stats", get(debug_pool_stats));
+    }
+    let app = app.with_state(config);
 
     // Step 6: Parse server address from environment or use default
     let server_addr = env::var("SERVER_ADDR")
@@ -227,8 +420,31 @@ async fn main() -> Result<()> {
         .parse()
         .context(format!("Invalid SERVER_ADDR: {}", server_addr))?;
 
-    // Step 7: Load TLS certificates
+    // Plain HTTP when TLS is terminated upstream (e.g. Kubernetes ingress)
+    let tls_enabled = env::var("TLS_ENABLED")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(true);
+    if !tls_enabled {
+        info!("🚀 Starting HTTP server at http://{} (TLS_ENABLED=false)", addr);
+        let listener = tokio::net::TcpListener::bind(addr)
+            .await
+            .context(format!("Failed to bind {}", addr))?;
+        axum::serve(listener, app.into_make_service_with_connect_info::<SocketAddr>())
+            .await
+            .context("HTTP server failed")?;
+        return Ok(());
+    }
+
+    // Step 7: Load TLS certificates (optionally reloaded when they change)
     let tls_config = load_tls_config().await?;
+    let tls_reload = env::var("TLS_RELOAD")
+        .ok()
+        .and_then(|v| v.parse().ok())
+        .unwrap_or(false);
+    if tls_reload {
+        tokio::spawn(watch_tls_files(tls_config.clone()));
+    }
 
     // Step 8: Start HTTPS server (blocks until shutdown)
     info!("🚀 Starting HTTPS server at https://{}", addr);
@@ -283,6 +499,19 @@ mod tests {
         }
     }
 
+    /// Test that unreadable TLS files have no modification time
+    #[test]
+    fn test_tls_file_mtimes_missing_files() {
+        assert_eq!(tls_file_mtimes("/nonexistent/cert.pem", "/nonexistent/key.pem"), (None, None));
+
+        let path = env::temp_dir().join(format!("indexer-tls-mtime-{}.pem", process::id()));
+        std::fs::write(&path, "test").unwrap();
+        let (cert, key) = tls_file_mtimes(path.to_str().unwrap(), "/nonexistent/key.pem");
+        assert!(cert.is_some());
+        assert!(key.is_none());
+        std::fs::remove_file(&path).unwrap();
+    }
+
     /// Test server address parsing
     #[test]
     fn test_server_address_parsing() {
@@ -345,6 +574,19 @@ mod tests {
         }
     }
 
+    /// Test that the default and documented log filters parse
+    #[test]
+    fn test_log_filters_parse() {
+        for filter in [
+            DEFAULT_LOG_FILTER,
+            "info,indexer::utils=warn",
+            "info,indexer::worker::metadata=debug",
+            "info,sqlx=warn",
+        ] {
+            assert!(EnvFilter::try_new(filter).is_ok(), "Should parse log filter: {}", filter);
+        }
+    }
+
     /// Test environment variable fallback for server address
     #[test]
     fn test_server_addr_env_fallback() {
@@ -6,19 +6,29 @@
 //! - Market data synchronization (daily)
 //! - Forex rate updates (configurable interval)
 //!
+//! plus an optional primary database health check with automatic failover
+//! and optional UniswapV2 swap indexing.
+//!
 //! All tasks run concurrently and independently, with automatic retry on failure.
+//! Each task can also be woken early through [`SyncTriggers`] (manager RPC).
 
 use std::sync::Arc;
-use tokio::sync::RwLock;
+use tokio::sync::{Mutex, Notify, OwnedMutexGuard, RwLock};
 use tokio::time::{Duration, Instant, sleep};
-use tracing::{info, error};
+use tracing::{Instrument, Span, info, info_span, instrument, error, warn};
 use anyhow::Result;
 
 use crate::config::Config;
+use crate::utils::{new_run_id, send_alert};
 use crate::worker::{
+    SyncStats,
+    dex::uniswapv2::index_uniswapv2_swaps,
     forex::update_forex,
     marketdata::sync_marketdata,
-    metadata::{fetch_token_metadata, fetch_nft_metadata, sync_nftmap, sync_tokenmap, update_metadata_from_blockscout},
+    metadata::{
+        fetch_token_metadata, fetch_nft_metadata, refresh_token_metadata, sync_nft_market_data, sync_nftmap,
+        sync_tokenmap, update_metadata_from_blockscout,
+    },
 };
 
 // ======================= Constants =======================
@@ -26,6 +36,173 @@ use crate::worker::{
 /// Daily task interval in seconds (24 hours)
 const DAILY_INTERVAL_SECS: u64 = 24 * 3600;
 
+/// Attempts per daily sync step before waiting for the next scheduled run
+const DAILY_TASK_MAX_ATTEMPTS: u32 = 3;
+
+/// Delay between attempts of a failed daily sync step (5 minutes)
+const DAILY_TASK_RETRY_DELAY: Duration = Duration::from_secs(300);
+
+/// Interval between primary health checks of the failover task
+const FAILOVER_CHECK_INTERVAL: Duration = Duration::from_secs(10);
+
+/// Consecutive failed health checks before switching to the standby
+const FAILOVER_FAILURE_THRESHOLD: u32 = 3;
+
+/// Interval between UniswapV2 swap polls
+const DEX_POLL_INTERVAL: Duration = Duration::from_secs(15);
+
+/// Task names recorded in `task_runs`
+const MARKETDATA_TASK: &str = "sync_marketdata";
+const FOREX_TASK: &str = "update_forex";
+
+// ======================= Triggers =======================
+
+/// Background sync pipelines that can be triggered on demand
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub enum SyncTarget {
+    Metadata,
+    MarketData,
+    Forex,
+}
+
+/// Wake-up handles and running guards of the background tasks
+///
+/// Notifying a handle cuts the task's current sleep short. If the task is
+/// busy, the notification is remembered and the task re-runs right after
+/// the current run finishes.
+///
+/// Every run of a pipeline, scheduled or inline (`run_now`), holds the
+/// pipeline's running guard, so the same pipeline never runs twice at once.
+#[derive(Debug, Clone, Default)]
+pub struct SyncTriggers {
+    pub metadata: Arc<Notify>,
+    pub marketdata: Arc<Notify>,
+    pub forex: Arc<Notify>,
+    metadata_running: Arc<Mutex<()>>,
+    marketdata_running: Arc<Mutex<()>>,
+    forex_running: Arc<Mutex<()>>,
+}
+
+impl SyncTriggers {
+    /// Wakes the task for `target`
+    pub fn trigger(&self, target: SyncTarget) {
+        self.handle(target).notify_one();
+        info!("⚡ {:?} sync triggered", target);
+    }
+
+    /// Returns the notify handle for `target`
+    fn handle(&self, target: SyncTarget) -> Arc<Notify> {
+        match target {
+            SyncTarget::Metadata => self.metadata.clone(),
+            SyncTarget::MarketData => self.marketdata.clone(),
+            SyncTarget::Forex => self.forex.clone(),
+        }
+    }
+
+    /// Waits for a run of `target` in progress to finish, then claims the pipeline
+    ///
+    /// The pipeline is released when the returned guard is dropped.
+    pub async fn start(&self, target: SyncTarget) -> OwnedMutexGuard<()> {
+        self.running(target).lock_owned().await
+    }
+
+    /// Claims the pipeline of `target`, or returns `None` if it is already running
+    pub fn try_start(&self, target: SyncTarget) -> Option<OwnedMutexGuard<()>> {
+        self.running(target).try_lock_owned().ok()
+    }
+
+    /// Returns the running guard of `target`
+    fn running(&self, target: SyncTarget) -> Arc<Mutex<()>> {
+        match target {
+            SyncTarget::Metadata => self.metadata_running.clone(),
+            SyncTarget::MarketData => self.marketdata_running.clone(),
+            SyncTarget::Forex => self.forex_running.clone(),
+        }
+    }
+}
+
+/// Creates the span of one pipeline run with a fresh `run_id`
+///
+/// Every log line emitted inside carries `pipeline` and `run_id`, so one
+/// run can be followed across workers, steps and retry attempts.
+fn run_span(pipeline: &'static str) -> Span {
+    info_span!("sync_run", pipeline, run_id = %new_run_id())
+}
+
+/// Sleeps for `secs` seconds, or until `trigger` is notified
+///
+/// # Returns
+/// `true` if the trigger cut the sleep short
+async fn sleep_or_trigger(secs: u64, trigger: &Notify) -> bool {
+    tokio::select! {
+        _ = sleep(Duration::from_secs(secs)) => false,
+        _ = trigger.notified() => true,
+    }
+}
+
+/// Clones the shared configuration for one sync step
+///
+/// Steps run for minutes, or hours on an initializing metadata run, so they
+/// work on a snapshot instead of holding the lock: the read API, the manager
+/// RPC and `failover_task` keep getting it meanwhile. The snapshot shares the
+/// pools, rate limiters and circuit breaker with the live configuration.
+async fn snapshot(cfg: &RwLock<Config>) -> Config {
+    cfg.read().await.clone()
+}
+
+/// Runs a metadata fetch on a snapshot, then stores its resume point
+///
+/// Only the final write of `token_update_id`/`nft_update_id` takes the
+/// write lock, after the fetch returned (successfully or not).
+async fn fetch_metadata_step(
+    cfg: &RwLock<Config>,
+    fetch: impl AsyncFnOnce(&mut Config) -> Result<SyncStats>,
+) -> Result<SyncStats> {
+    let mut config = snapshot(cfg).await;
+    let result = fetch(&mut config).await;
+
+    let mut cfg_write = cfg.write().await;
+    cfg_write.token_update_id = config.token_update_id;
+    cfg_write.nft_update_id = config.nft_update_id;
+    result
+}
+
+// ======================= Last Successful Run =======================
+
+/// Whether a success `elapsed` ago is too recent to run again (`min_interval_secs` 0 disables the guard)
+fn within_min_interval(elapsed: Duration, min_interval_secs: u64) -> bool {
+    min_interval_secs > 0 && elapsed < Duration::from_secs(min_interval_secs)
+}
+
+/// Returns how long ago `task` last succeeded, if that is within `min_interval_secs`
+///
+/// Lookup failures are logged and count as "not recent", so a database
+/// hiccup never suppresses a sync.
+async fn ran_recently(cfg: &Arc<RwLock<Config>>, task: &str, min_interval_secs: u64) -> Option<Duration> {
+    if min_interval_secs == 0 {
+        return None;
+    }
+    let db = cfg.read().await.postgres_db.clone();
+    let last_success = match db.last_task_success(task).await {
+        Ok(last_success) => last_success?,
+        Err(e) => {
+            warn!("⚠️ Failed to read the last run of {}: {:#}", task, e);
+            return None;
+        }
+    };
+    // A timestamp in the future (clock skew) counts as just now
+    let elapsed = (chrono::Utc::now() - last_success).to_std().unwrap_or_default();
+    within_min_interval(elapsed, min_interval_secs).then_some(elapsed)
+}
+
+/// Persists a successful run of `task` (failures are only logged)
+async fn record_task_success(cfg: &Arc<RwLock<Config>>, task: &str) {
+    let db = cfg.read().await.postgres_db.clone();
+    if let Err(e) = db.record_task_success(task).await {
+        warn!("⚠️ Failed to record the run of {}: {:#}", task, e);
+    }
+}
+
 // ======================= Task Runner =======================
 
 /// Generic safe task executor with error handling and timing
@@ -55,109 +232,292 @@ where
     F: FnOnce() -> Fut + Send + 'static,
     Fut: std::future::Future<Output = Result<()>> + Send,
 {
-    let start = Instant::now();
-    match task().await {
-        Ok(_) => {
-            info!(elapsed=?start.elapsed(), "✅ {} finished", name);
+    safe_run_value(name, task).await.is_some()
+}
+
+/// Like [`safe_run`], but hands back the task's successful output
+///
+/// Equivalent to [`safe_run_with_retry`] with a single attempt.
+///
+/// # Returns
+/// * `Some(T)` - Task completed successfully
+/// * `None` - Task failed with error (already logged)
+pub async fn safe_run_value<T, F, Fut>(name: &str, task: F) -> Option<T>
+where
+    F: FnOnce() -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Result<T>> + Send,
+{
+    let mut task = Some(task);
+    safe_run_with_retry(name, 1, Duration::ZERO, move || {
+        (task.take().expect("single-attempt task is only called once"))()
+    })
+    .await
+}
+
+/// Safe task executor that retries failed attempts
+///
+/// Runs `task` up to `max_attempts` times, sleeping `retry_delay` between
+/// attempts, so transient failures (DB hiccups, API timeouts) don't wait a
+/// whole scheduling interval. Every failure is logged.
+///
+/// # Arguments
+/// * `name` - Task name for logging purposes
+/// * `max_attempts` - Total attempts, including the first (values below 1 count as 1)
+/// * `retry_delay` - Sleep between attempts
+/// * `task` - Async function to execute; called once per attempt
+///
+/// # Returns
+/// * `Some(T)` - An attempt completed successfully
+/// * `None` - All attempts failed
+pub async fn safe_run_with_retry<T, F, Fut>(
+    name: &str,
+    max_attempts: u32,
+    retry_delay: Duration,
+    mut task: F,
+) -> Option<T>
+where
+    F: FnMut() -> Fut + Send + 'static,
+    Fut: std::future::Future<Output = Result<T>> + Send,
+{
+    let max_attempts = max_attempts.max(1);
+    for attempt in 1..=max_attempts {
+        let start = Instant::now();
+        match task().await {
+            Ok(value) => {
+                info!(elapsed=?start.elapsed(), "✅ {} finished", name);
+                return Some(value);
+            }
+            Err(e) if attempt < max_attempts => {
+                warn!(
+                    error=?e,
+                    "⚠️ {} failed (attempt {}/{}), retrying in {:?}",
+                    name, attempt, max_attempts, retry_delay
+                );
+                sleep(retry_delay).await;
+            }
+            Err(e) => {
+                if max_attempts > 1 {
+                    error!(error=?e, "❌ {} failed after {} attempts", name, max_attempts);
+                } else {
+                    error!(error=?e, "❌ {} failed", name);
+                }
+            }
+        }
+    }
+    None
+}
+
+/// Folds a step's outcome into the pipeline stats, returning whether it succeeded
+fn record_step(stats: &mut SyncStats, step: Option<SyncStats>) -> bool {
+    match step {
+        Some(step_stats) => {
+            stats.merge(step_stats);
             true
         }
-        Err(e) => {
-            error!(error=?e, "❌ {} failed", name);
-            false
+        None => false,
+    }
+}
+
+/// Runs one sync pipeline immediately and returns its stats
+///
+/// Used by the manager RPC for inline (`run_now`) syncs, which claim the
+/// pipeline with [`SyncTriggers::try_start`] first. Errors are logged by
+/// the underlying runners as usual.
+pub async fn run_sync_now(cfg: Arc<RwLock<Config>>, target: SyncTarget) -> Result<SyncStats> {
+    match target {
+        SyncTarget::Metadata => {
+            // On-demand runs report failures right away instead of retrying
+            let (all_steps_succeeded, stats) = run_metadata_pipeline(&cfg, 1).await;
+            if !all_steps_succeeded {
+                anyhow::bail!("One or more metadata steps failed, see logs");
+            }
+            Ok(stats)
+        }
+        SyncTarget::MarketData => {
+            let stats = {
+                let config = snapshot(&cfg).await;
+                sync_marketdata(&config).instrument(run_span("marketdata")).await?
+            };
+            record_task_success(&cfg, MARKETDATA_TASK).await;
+            Ok(stats)
+        }
+        SyncTarget::Forex => {
+            let stats = {
+                let config = snapshot(&cfg).await;
+                update_forex(&config).instrument(run_span("forex")).await?
+            };
+            record_task_success(&cfg, FOREX_TASK).await;
+            Ok(stats)
         }
     }
 }
 
 // ======================= Metadata Task =======================
 
-/// Daily metadata synchronization task
+/// Runs every step of the metadata pipeline once
 ///
-/// This task runs continuously with a 24-hour interval and performs:
+/// Steps:
 /// 1. Token mapping synchronization from CoinGecko
 /// 2. NFT mapping synchronization from CoinGecko
 /// 3. Incremental token metadata fetch (new tokens only)
-/// 4. Incremental NFT metadata fetch (new NFTs only)
-/// 5. Contract verification data from Blockscout
+/// 4. Re-check of stored token metadata older than `METADATA_REFRESH_DAYS`
+/// 5. Incremental NFT metadata fetch (new NFTs only)
+/// 6. Contract verification data from Blockscout
+/// 7. NFT collection market data (floor price, volume, market cap)
+///
+/// Each step is wrapped in `safe_run_with_retry`, so a failing step is
+/// retried up to `max_attempts` times, then logged, and the remaining
+/// steps still run.
+///
+/// # Returns
+/// `(all_steps_succeeded, accumulated stats of the successful steps)`
+#[instrument(name = "sync_run", skip_all, fields(pipeline = "metadata", run_id = %new_run_id()))]
+async fn run_metadata_pipeline(
+    cfg: &Arc<RwLock<Config>>,
+    max_attempts: u32,
+) -> (bool, SyncStats) {
+    let mut stats = SyncStats::default();
+
+    // Track success of all steps in this iteration
+    let mut all_steps_succeeded = true;
+
+    // Step 1: Sync token mapping from CoinGecko API
+    // Populates tokenmap table with token addresses across all chains
+    let step = safe_run_with_retry("sync_tokenmap", max_attempts, DAILY_TASK_RETRY_DELAY, {
+        let cfg = cfg.clone();
+        move || {
+            let cfg = cfg.clone();
+            async move {
+                let config = snapshot(&cfg).await;
+                sync_tokenmap(&config).await
+            }
+        }
+    }).await;
+    all_steps_succeeded &= record_step(&mut stats, step);
+
+    // Step 2: Sync NFT mapping from CoinGecko API
+    // Populates nftmap table with NFT collection addresses
+    let step = safe_run_with_retry("sync_nftmap", max_attempts, DAILY_TASK_RETRY_DELAY, {
+        let cfg = cfg.clone();
+        move || {
+            let cfg = cfg.clone();
+            async move {
+                let config = snapshot(&cfg).await;
+                sync_nftmap(&config).await
+            }
+        }
+    }).await;
+    all_steps_succeeded &= record_step(&mut stats, step);
+
+    // Step 3: Fetch metadata for new tokens (incremental)
+    // Updates config.token_update_id for resume capability
+    let step = safe_run_with_retry("fetch_token_metadata", max_attempts, DAILY_TASK_RETRY_DELAY, {
+        let cfg = cfg.clone();
+        move || {
+            let cfg = cfg.clone();
+            async move { fetch_metadata_step(&cfg, fetch_token_metadata).await }
+        }
+    }).await;
+    all_steps_succeeded &= record_step(&mut stats, step);
+
+    // Step 4: Re-check a batch of stored token metadata (updates, 404 deactivation)
+    let step = safe_run_with_retry("refresh_token_metadata", max_attempts, DAILY_TASK_RETRY_DELAY, {
+        let cfg = cfg.clone();
+        move || {
+            let cfg = cfg.clone();
+            async move {
+                let config = snapshot(&cfg).await;
+                refresh_token_metadata(&config).await
+            }
+        }
+    }).await;
+    all_steps_succeeded &= record_step(&mut stats, step);
+
+    // Step 5: Fetch metadata for new NFTs (incremental)
+    // Updates config.nft_update_id for resume capability
+    let step = safe_run_with_retry("fetch_nft_metadata", max_attempts, DAILY_TASK_RETRY_DELAY, {
+        let cfg = cfg.clone();
+        move || {
+            let cfg = cfg.clone();
+            async move { fetch_metadata_step(&cfg, fetch_nft_metadata).await }
+        }
+    }).await;
+    all_steps_succeeded &= record_step(&mut stats, step);
+
+    // Step 6: Update metadata with contract verification info from Blockscout
+    // Enriches existing metadata with verification status and risk assessment
+    let step = safe_run_with_retry("update_metadata_from_blockscout", max_attempts, DAILY_TASK_RETRY_DELAY, {
+        let cfg = cfg.clone();
+        move || {
+            let cfg = cfg.clone();
+            async move {
+                let config = snapshot(&cfg).await;
+                update_metadata_from_blockscout(&config).await
+            }
+        }
+    }).await;
+    all_steps_succeeded &= record_step(&mut stats, step);
+
+    // Step 7: Refresh NFT floor price, volume and market cap
+    let step = safe_run_with_retry("sync_nft_market_data", max_attempts, DAILY_TASK_RETRY_DELAY, {
+        let cfg = cfg.clone();
+        move || {
+            let cfg = cfg.clone();
+            async move {
+                let config = snapshot(&cfg).await;
+                sync_nft_market_data(&config).await
+            }
+        }
+    }).await;
+    all_steps_succeeded &= record_step(&mut stats, step);
+
+    (all_steps_succeeded, stats)
+}
+
+/// Daily metadata synchronization task
+///
+/// This task runs continuously with a 24-hour interval and executes
+/// [`run_metadata_pipeline`] on every iteration.
 ///
 /// # Workflow
 /// - sync_tokenmap: Updates tokenmap table with latest token addresses
 /// - sync_nftmap: Updates nftmap table with latest NFT collections
 /// - fetch_token_metadata: Fetches metadata for new tokens (skips existing)
+/// - refresh_token_metadata: Re-checks stored token metadata, deactivating and delisting 404s
 /// - fetch_nft_metadata: Fetches metadata for new NFTs (skips existing)
 /// - update_metadata_from_blockscout: Enriches metadata with verification status
+/// - sync_nft_market_data: Refreshes NFT floor price, volume and market cap
 ///
 /// # Error Handling
-/// Each sub-task is wrapped in `safe_run`, which logs errors but continues execution.
-/// Individual task failures don't stop the pipeline.
+/// Each sub-task is wrapped in `safe_run_with_retry`: a failing step is retried
+/// up to 3 times, 5 minutes apart, then logged. Individual task failures don't
+/// stop the pipeline.
 ///
 /// # Arguments
 /// * `cfg` - Shared configuration (wrapped in Arc<RwLock> for thread-safety)
 ///
 /// # Note
-/// - Uses write lock for metadata fetch tasks (to update progress tracking)
-/// - Uses read lock for other tasks (read-only operations)
+/// - Every step runs on a config snapshot, holding no lock while it runs
+/// - Metadata fetch steps write their progress back under a short write lock
 /// - Runs indefinitely until process termination
+/// - The daily sleep is cut short by `SyncTriggers::metadata`
 async fn metadata_task(cfg: Arc<RwLock<Config>>) {
     // Track if this is the first run (for initialization)
-    let mut is_first_run = cfg.read().await.is_initializing_metadata;
+    let (mut is_first_run, triggers) = {
+        let cfg_read = cfg.read().await;
+        (cfg_read.is_initializing_metadata, cfg_read.sync_triggers.clone())
+    };
 
     loop {
         let pipeline_start = Instant::now();
-        
-        // Track success of all steps in this iteration
-        let mut all_steps_succeeded = true;
-
-        // Step 1: Sync token mapping from CoinGecko API
-        // Populates tokenmap table with token addresses across all chains
-        all_steps_succeeded &= safe_run("sync_tokenmap", {
-            let cfg = cfg.clone();
-            move || async move {
-                let cfg_read = cfg.read().await;
-                sync_tokenmap(&*cfg_read).await
-            }
-        }).await;
-
-        // Step 2: Sync NFT mapping from CoinGecko API
-        // Populates nftmap table with NFT collection addresses
-        all_steps_succeeded &= safe_run("sync_nftmap", {
-            let cfg = cfg.clone();
-            move || async move {
-                let cfg_read = cfg.read().await;
-                sync_nftmap(&*cfg_read).await
-            }
-        }).await;
-
-        // Step 3: Fetch metadata for new tokens (incremental)
-        // Uses write lock to update config.token_update_id for resume capability
-        all_steps_succeeded &= safe_run("fetch_token_metadata", {
-            let cfg = cfg.clone();
-            move || async move {
-                let mut cfg_write = cfg.write().await;
-                fetch_token_metadata(&mut *cfg_write).await
-            }
-        }).await;
-
-        // Step 4: Fetch metadata for new NFTs (incremental)
-        // Uses write lock to update config.nft_update_id for resume capability
-        all_steps_succeeded &= safe_run("fetch_nft_metadata", {
-            let cfg = cfg.clone();
-            move || async move {
-                let mut cfg_write = cfg.write().await;
-                fetch_nft_metadata(&mut *cfg_write).await
-            }
-        }).await;
 
-        // Step 5: Update metadata with contract verification info from Blockscout
-        // Enriches existing metadata with verification status and risk assessment
-        all_steps_succeeded &= safe_run("update_metadata_from_blockscout", {
-            let cfg = cfg.clone();
-            move || async move {
-                let cfg_read = cfg.read().await;
-                update_metadata_from_blockscout(&*cfg_read).await
-            }
-        }).await;
+        // Waits for an inline run started through the manager RPC
+        let (all_steps_succeeded, _stats) = {
+            let _running = triggers.start(SyncTarget::Metadata).await;
+            run_metadata_pipeline(&cfg, DAILY_TASK_MAX_ATTEMPTS).await
+        };
 
-        // Step 6: Mark initialization as complete ONLY if all steps succeeded
+        // Mark initialization as complete ONLY if all steps succeeded
         // This ensures we don't incorrectly mark initialization as complete
         // when there were failures that need to be retried
         if is_first_run && all_steps_succeeded {
@@ -175,7 +535,7 @@ async fn metadata_task(cfg: Arc<RwLock<Config>>) {
             "✅ daily metadata pipeline finished, sleeping {}s...",
             DAILY_INTERVAL_SECS
         );
-        sleep(Duration::from_secs(DAILY_INTERVAL_SECS)).await;
+        sleep_or_trigger(DAILY_INTERVAL_SECS, &triggers.metadata).await;
     }
 }
 
@@ -194,26 +554,50 @@ async fn metadata_task(cfg: Arc<RwLock<Config>>) {
 /// the marketdata table in PostgreSQL.
 ///
 /// # Schedule
-/// Runs once every 24 hours
+/// Runs once every 24 hours, or earlier when `SyncTriggers::marketdata` fires.
+/// A scheduled run (including the first one after startup) is skipped if the
+/// last success was less than `MARKETDATA_MIN_INTERVAL_SECS` ago; triggered
+/// runs always execute.
 ///
 /// # Error Handling
-/// Failures are logged but don't stop the task loop. The task will retry
-/// on the next scheduled run.
+/// A failed sync is retried up to 3 times, 5 minutes apart; after that the
+/// failure is logged and the task waits for the next scheduled run.
 ///
 /// # Arguments
 /// * `cfg` - Shared configuration (uses read lock for read-only access)
 async fn marketdata_task(cfg: Arc<RwLock<Config>>) {
+    let triggers = cfg.read().await.sync_triggers.clone();
+    let mut triggered = false;
+
     loop {
         let start = Instant::now();
+        let running = triggers.start(SyncTarget::MarketData).await;
+        let min_interval_secs = cfg.read().await.marketdata_min_interval_secs;
 
-        // Fetch latest market data from CoinGecko for all tracked tokens
-        safe_run("sync_marketdata", {
-            let cfg = cfg.clone();
-            move || async move {
-                let cfg_read = cfg.read().await;
-                sync_marketdata(&*cfg_read).await
+        // Skip a scheduled run right after a manual one (or a restart)
+        let recent = if triggered { None } else { ran_recently(&cfg, MARKETDATA_TASK, min_interval_secs).await };
+        if let Some(ago) = recent {
+            info!("⏭️ sync_marketdata skipped, ran {} min ago", ago.as_secs() / 60);
+        } else {
+            // Fetch latest market data from CoinGecko for all tracked tokens
+            let result = safe_run_with_retry("sync_marketdata", DAILY_TASK_MAX_ATTEMPTS, DAILY_TASK_RETRY_DELAY, {
+                let cfg = cfg.clone();
+                move || {
+                    let cfg = cfg.clone();
+                    async move {
+                        let config = snapshot(&cfg).await;
+                        sync_marketdata(&config).await
+                    }
+                }
+            })
+            .instrument(run_span("marketdata"))
+            .await;
+            if result.is_some() {
+                record_task_success(&cfg, MARKETDATA_TASK).await;
             }
-        }).await;
+        }
+
+        drop(running);
 
         // Sleep for 24 hours before next sync
         info!(
@@ -221,7 +605,7 @@ async fn marketdata_task(cfg: Arc<RwLock<Config>>) {
             "✅ daily marketdata finished, sleeping {}s...",
             DAILY_INTERVAL_SECS
         );
-        sleep(Duration::from_secs(DAILY_INTERVAL_SECS)).await;
+        triggered = sleep_or_trigger(DAILY_INTERVAL_SECS, &triggers.marketdata).await;
     }
 }
 
@@ -239,6 +623,9 @@ async fn marketdata_task(cfg: Arc<RwLock<Config>>) {
 /// # Schedule
 /// Runs at configurable intervals (default: 1 hour)
 /// Interval can be adjusted via `config.set_forex_interval_secs()`
+/// and the wait is cut short when `SyncTriggers::forex` fires.
+/// A scheduled run is skipped if the last success was less than
+/// `FOREX_MIN_INTERVAL_SECS` ago; triggered runs always execute.
 ///
 /// # Use Case
 /// Forex rates are used to convert token prices to different fiat currencies
@@ -250,17 +637,34 @@ async fn marketdata_task(cfg: Arc<RwLock<Config>>) {
 /// # Arguments
 /// * `cfg` - Shared configuration (uses read lock to fetch interval setting)
 async fn forex_task(cfg: Arc<RwLock<Config>>) {
+    let triggers = cfg.read().await.sync_triggers.clone();
+    let mut triggered = false;
+
     loop {
         let start = Instant::now();
+        let running = triggers.start(SyncTarget::Forex).await;
+        let min_interval_secs = cfg.read().await.forex_min_interval_secs;
 
-        // Fetch latest forex exchange rates from OpenExchangeRates API
-        safe_run("update_forex", {
-            let cfg = cfg.clone();
-            move || async move {
-                let cfg_read = cfg.read().await;
-                update_forex(&*cfg_read).await
+        let recent = if triggered { None } else { ran_recently(&cfg, FOREX_TASK, min_interval_secs).await };
+        if let Some(ago) = recent {
+            info!("⏭️ update_forex skipped, ran {} min ago", ago.as_secs() / 60);
+        } else {
+            // Fetch latest forex exchange rates from OpenExchangeRates API
+            let succeeded = safe_run("update_forex", {
+                let cfg = cfg.clone();
+                move || async move {
+                    let config = snapshot(&cfg).await;
+                    update_forex(&config).await.map(|_| ())
+                }
+            })
+            .instrument(run_span("forex"))
+            .await;
+            if succeeded {
+                record_task_success(&cfg, FOREX_TASK).await;
             }
-        }).await;
+        }
+
+        drop(running);
 
         // Get configurable sleep interval (allows runtime adjustment)
         let sleep_secs = cfg.read().await.forex_interval_secs;
@@ -269,7 +673,151 @@ async fn forex_task(cfg: Arc<RwLock<Config>>) {
             "✅ forex update finished, sleeping {}s...",
             sleep_secs
         );
-        sleep(Duration::from_secs(sleep_secs)).await;
+        triggered = sleep_or_trigger(sleep_secs, &triggers.forex).await;
+    }
+}
+
+// ======================= Failover Task =======================
+
+/// Watches the primary database and fails over to the standby
+///
+/// # Workflow
+/// 1. Every `FAILOVER_CHECK_INTERVAL`, run [`PostgresDb::health_check`](crate::config::PostgresDb::health_check)
+/// 2. After `FAILOVER_FAILURE_THRESHOLD` consecutive failures, switch to
+///    `STANDBY_DATABASE_URL` through `update_db_url` (same validation as the RPC)
+/// 3. Log loudly and post to `ALERT_WEBHOOK_URL`
+///
+/// # Notes
+/// - Only runs with `AUTO_FAILOVER=true` and a standby configured
+/// - The standby must already accept writes (promoted by the HA tooling);
+///   a read-only standby is rejected and the switch is retried next check
+/// - After a failover the standby slot is cleared, so it happens at most
+///   once until the `failback` RPC restores the original primary
+///
+/// # Arguments
+/// * `cfg` - Shared configuration
+async fn failover_task(cfg: Arc<RwLock<Config>>) {
+    {
+        let cfg_read = cfg.read().await;
+        if !cfg_read.auto_failover {
+            return;
+        }
+        if cfg_read.standby_db_url.is_none() {
+            warn!("⚠️ AUTO_FAILOVER is set but STANDBY_DATABASE_URL is not, failover disabled");
+            return;
+        }
+    }
+    info!("🛡️ Automatic failover enabled");
+
+    let mut consecutive_failures = 0u32;
+    loop {
+        sleep(FAILOVER_CHECK_INTERVAL).await;
+
+        // Check on a clone so the lock is not held across the probe
+        let db = cfg.read().await.postgres_db.clone();
+        match db.health_check().await {
+            Ok(()) => {
+                if consecutive_failures > 0 {
+                    info!("✅ Primary database healthy again");
+                }
+                consecutive_failures = 0;
+                continue;
+            }
+            Err(e) => {
+                consecutive_failures += 1;
+                warn!(
+                    "⚠️ Primary health check failed ({}/{}): {:#}",
+                    consecutive_failures, FAILOVER_FAILURE_THRESHOLD, e
+                );
+            }
+        }
+
+        if consecutive_failures < FAILOVER_FAILURE_THRESHOLD {
+            continue;
+        }
+
+        let Some(switched) = switch_to_standby(&cfg).await else {
+            continue; // Already failed over; wait for a failback
+        };
+        let message = match switched {
+            Ok(()) => {
+                consecutive_failures = 0;
+                error!("🚨 FAILOVER: primary database unreachable, switched to standby");
+                "🚨 Indexer failed over to the standby database after the primary failed health checks".to_string()
+            }
+            Err(e) => {
+                error!("🚨 FAILOVER FAILED: could not switch to standby: {}", e);
+                format!("🚨 Indexer primary database is down and failover to the standby failed: {}", e)
+            }
+        };
+
+        let (client, webhook_url) = {
+            let cfg_read = cfg.read().await;
+            (cfg_read.http_client.clone(), cfg_read.alert_webhook_url.clone())
+        };
+        send_alert(&client, webhook_url.as_deref(), &message).await;
+    }
+}
+
+/// Makes the standby the primary, remembering the old primary for `failback`
+///
+/// # Returns
+/// * `None` - No standby left (already failed over)
+/// * `Some(Ok(()))` - Switched, the standby slot is cleared
+/// * `Some(Err(e))` - The standby was rejected, the primary is unchanged
+async fn switch_to_standby(cfg: &RwLock<Config>) -> Option<std::result::Result<(), sqlx::Error>> {
+    let mut cfg_write = cfg.write().await;
+    let standby_url = cfg_write.standby_db_url.clone()?;
+    let old_url = cfg_write.postgres_db.primary_db_url.clone();
+
+    let result = cfg_write.update_db_url(standby_url).await;
+    if result.is_ok() {
+        cfg_write.failed_over_from = Some(old_url);
+        cfg_write.standby_db_url = None;
+    }
+    Some(result)
+}
+
+// ======================= DEX Task =======================
+
+/// Polls UniswapV2 `Swap` events of the configured pairs
+///
+/// # Notes
+/// - Only runs with both `UNISWAP_PAIRS` and `DEX_RPC_URL` set
+/// - The next start block is kept in memory; after a restart indexing
+///   resumes after the highest block stored in `swaps`
+///
+/// # Arguments
+/// * `cfg` - Shared configuration
+async fn dex_task(cfg: Arc<RwLock<Config>>) {
+    {
+        let cfg_read = cfg.read().await;
+        if cfg_read.uniswap_pairs.is_empty() {
+            return;
+        }
+        if cfg_read.dex_rpc_url.is_none() {
+            warn!("⚠️ UNISWAP_PAIRS is set but DEX_RPC_URL is not, swap indexing disabled");
+            return;
+        }
+        info!("🦄 UniswapV2 swap indexing enabled for {} pair(s)", cfg_read.uniswap_pairs.len());
+    }
+
+    let mut next_block = None;
+    loop {
+        let result = safe_run_value("index_uniswapv2_swaps", {
+            let cfg = cfg.clone();
+            move || async move {
+                let config = snapshot(&cfg).await;
+                index_uniswapv2_swaps(&config, next_block).await
+            }
+        })
+        .instrument(run_span("dex"))
+        .await;
+
+        if let Some((_, block)) = result {
+            next_block = Some(block);
+        }
+        sleep(DEX_POLL_INTERVAL).await;
     }
 }
 
@@ -277,11 +825,13 @@ async fn forex_task(cfg: Arc<RwLock<Config>>) {
 
 /// Starts all background tasks concurrently
 ///
-/// This is the main entry point for the task scheduler. It launches three
+/// This is the main entry point for the task scheduler. It launches five
 /// independent tasks that run in parallel:
 /// 1. **metadata_task** - Daily metadata synchronization (24h interval)
 /// 2. **marketdata_task** - Daily market data updates (24h interval)
 /// 3. **forex_task** - Forex rate updates (configurable interval)
+/// 4. **failover_task** - Primary health checks (only with `AUTO_FAILOVER=true`)
+/// 5. **dex_task** - UniswapV2 swap polling (only with `UNISWAP_PAIRS` and `DEX_RPC_URL`)
 ///
 /// # Concurrency Model
 /// Uses `tokio::join!` to run all tasks concurrently. All tasks are long-running
@@ -308,7 +858,7 @@ async fn forex_task(cfg: Arc<RwLock<Config>>) {
 ///
 /// #[tokio::main]
 /// async fn main() {
-///     let config = Arc::new(RwLock::new(Config::from_env()));
+///     let config = Arc::new(RwLock::new(Config::from_env().unwrap()));
 ///     start_all_tasks(config).await; // Runs forever
 /// }
 /// ```
@@ -316,7 +866,9 @@ pub async fn start_all_tasks(cfg: Arc<RwLock<Config>>) {
     tokio::join!(
         metadata_task(cfg.clone()),
         marketdata_task(cfg.clone()),
-        forex_task(cfg)
+        forex_task(cfg.clone()),
+        failover_task(cfg.clone()),
+        dex_task(cfg)
     );
 }
 
@@ -384,6 +936,8 @@ mod tests {
     /// Test constant values
     #[test]
     fn test_constants() {
+        assert_eq!(DAILY_TASK_MAX_ATTEMPTS, 3);
+        assert_eq!(DAILY_TASK_RETRY_DELAY, Duration::from_secs(300));
         assert_eq!(DAILY_INTERVAL_SECS, 86400, "Daily interval should be 24 hours (86400 seconds)");
         assert_eq!(DAILY_INTERVAL_SECS, 24 * 3600, "Daily interval calculation should be correct");
     }
@@ -446,7 +1000,6 @@ mod tests {
         assert!(!r3, "Custom error should return false");
 
         // If we reach here, error logging didn't panic
-        assert!(true, "Error logging should not panic");
     }
 
     /// Test all steps success tracking
@@ -469,18 +1022,128 @@ mod tests {
         assert!(!all_succeeded, "Should not succeed if any step fails");
     }
 
+    /// Test that safe_run_value returns the task output
+    #[tokio::test]
+    async fn test_safe_run_value() {
+        let ok = safe_run_value("value_task", || async { Ok(42) }).await;
+        assert_eq!(ok, Some(42));
+
+        let failed: Option<i32> = safe_run_value("value_task", || async {
+            Err(anyhow::anyhow!("Failed"))
+        })
+        .await;
+        assert!(failed.is_none());
+    }
+
+    /// Test that safe_run_with_retry retries until an attempt succeeds
+    #[tokio::test]
+    async fn test_safe_run_with_retry_recovers() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let result = safe_run_with_retry("flaky_task", 3, Duration::from_millis(1), {
+            let calls = calls.clone();
+            move || {
+                let attempt = calls.fetch_add(1, Ordering::SeqCst) + 1;
+                async move {
+                    if attempt < 3 {
+                        Err(anyhow::anyhow!("transient failure {}", attempt))
+                    } else {
+                        Ok(attempt)
+                    }
+                }
+            }
+        })
+        .await;
+
+        assert_eq!(result, Some(3));
+        assert_eq!(calls.load(Ordering::SeqCst), 3);
+    }
+
+    /// Test that safe_run_with_retry gives up after max_attempts
+    #[tokio::test]
+    async fn test_safe_run_with_retry_exhausted() {
+        let calls = Arc::new(AtomicUsize::new(0));
+        let result: Option<()> = safe_run_with_retry("broken_task", 2, Duration::ZERO, {
+            let calls = calls.clone();
+            move || {
+                calls.fetch_add(1, Ordering::SeqCst);
+                async { Err(anyhow::anyhow!("always fails")) }
+            }
+        })
+        .await;
+
+        assert!(result.is_none());
+        assert_eq!(calls.load(Ordering::SeqCst), 2);
+    }
+
+    /// Test the last-success guard threshold
+    #[test]
+    fn test_within_min_interval() {
+        assert!(within_min_interval(Duration::from_secs(5 * 60), 3600));
+        assert!(!within_min_interval(Duration::from_secs(3600), 3600));
+        assert!(!within_min_interval(Duration::from_secs(2 * 3600), 3600));
+        // 0 disables the guard
+        assert!(!within_min_interval(Duration::ZERO, 0));
+    }
+
+    /// Test that record_step merges stats only for successful steps
+    #[test]
+    fn test_record_step() {
+        let mut stats = SyncStats::default();
+        assert!(record_step(&mut stats, Some(SyncStats { inserted: 3, updated: 1, pages: 2, fetched: 0, deactivated: 1, delisted_count: 1 })));
+        assert!(!record_step(&mut stats, None));
+        assert!(record_step(&mut stats, Some(SyncStats { inserted: 2, updated: 0, pages: 1, fetched: 0, deactivated: 0, delisted_count: 0 })));
+        assert_eq!(stats.inserted, 5);
+        assert_eq!(stats.updated, 1);
+        assert_eq!(stats.pages, 3);
+        assert_eq!(stats.deactivated, 1);
+        assert_eq!(stats.delisted_count, 1);
+    }
+
+    /// Test that a trigger wakes a sleeping task early
+    #[tokio::test]
+    async fn test_sleep_or_trigger_wakes_early() {
+        let triggers = SyncTriggers::default();
+        triggers.trigger(SyncTarget::Forex);
+
+        // The stored permit makes the (very long) sleep return immediately
+        let result = tokio::time::timeout(
+            Duration::from_secs(1),
+            sleep_or_trigger(DAILY_INTERVAL_SECS, &triggers.forex),
+        )
+        .await;
+        assert!(result.is_ok(), "Trigger should cut the sleep short");
+    }
+
+    /// Test that a running pipeline can't be started again until released
+    #[tokio::test]
+    async fn test_sync_running_guard() {
+        let triggers = SyncTriggers::default();
+        let shared = triggers.clone();
+
+        let running = triggers.try_start(SyncTarget::Metadata).expect("idle pipeline");
+        assert!(shared.try_start(SyncTarget::Metadata).is_none(), "Clones share the guard");
+        assert!(shared.try_start(SyncTarget::Forex).is_some(), "Pipelines are guarded separately");
+
+        // A scheduled run waits for the inline one
+        let waiting = tokio::time::timeout(Duration::from_millis(50), shared.start(SyncTarget::Metadata)).await;
+        assert!(waiting.is_err());
+
+        drop(running);
+        assert!(shared.try_start(SyncTarget::Metadata).is_some());
+    }
+
     /// Test initialization flag behavior
     #[tokio::test]
     async fn test_initialization_flag() {
         use crate::config::Config;
         
         // Create config with initialization flag set to true
-        let config = Arc::new(RwLock::new(Config::from_env()));
+        let config = Arc::new(RwLock::new(Config::for_tests()));
         
         // Verify initial state
         {
             let cfg = config.read().await;
-            assert_eq!(cfg.is_initializing_metadata, true, "Should start with is_initializing_metadata = true");
+            assert!(cfg.is_initializing_metadata, "Should start with is_initializing_metadata = true");
         }
         
         // Simulate what metadata_task does after first run
@@ -492,7 +1155,48 @@ mod tests {
         // Verify flag was changed
         {
             let cfg = config.read().await;
-            assert_eq!(cfg.is_initializing_metadata, false, "Should be false after initialization");
+            assert!(!cfg.is_initializing_metadata, "Should be false after initialization");
         }
     }
+
+    /// Test that failover gets the config lock while a metadata fetch is in progress
+    #[tokio::test]
+    async fn test_failover_proceeds_during_metadata_run() {
+        let cfg = Arc::new(RwLock::new(Config::for_tests()));
+        // Rejected without connecting, so the switch returns right away
+        cfg.write().await.standby_db_url = Some("not a database url".to_string());
+
+        let (started_tx, started_rx) = tokio::sync::oneshot::channel();
+        let release = Arc::new(Notify::new());
+        let run = tokio::spawn({
+            let (cfg, release) = (cfg.clone(), release.clone());
+            async move {
+                fetch_metadata_step(&cfg, async |config: &mut Config| {
+                    started_tx.send(()).unwrap();
+                    release.notified().await;
+                    config.token_update_id = 42;
+                    Ok(SyncStats::default())
+                })
+                .await
+            }
+        });
+        started_rx.await.unwrap();
+
+        let switched = tokio::time::timeout(Duration::from_secs(5), switch_to_standby(&cfg))
+            .await
+            .expect("failover must not wait for the metadata run");
+        assert!(matches!(switched, Some(Err(_))), "Invalid standby should be rejected");
+        assert!(cfg.read().await.failed_over_from.is_none());
+
+        release.notify_one();
+        run.await.unwrap().unwrap();
+        assert_eq!(cfg.read().await.token_update_id, 42, "Resume point should be written back");
+    }
+
+    /// Test that a switch without a standby is a no-op
+    #[tokio::test]
+    async fn test_switch_to_standby_without_standby() {
+        let cfg = RwLock::new(Config::for_tests());
+        assert!(switch_to_standby(&cfg).await.is_none());
+    }
 }
@@ -3,21 +3,38 @@
 //! This module provides common utility functions used across the indexer service.
 //! Currently includes:
 //! - HTTP request helpers with retry logic
+//! - Circuit breaker for failing upstream APIs
+//! - Token-bucket rate limiter for upstream API quotas (and per client IP)
+//! - EVM address validation (EIP-55 checksums)
+//! - Operator alert webhook
 //! - JSON parsing utilities
 //! - Error handling wrappers
 
-use std::time::Duration;
+use std::collections::HashMap;
+use std::net::IpAddr;
+use std::sync::Mutex;
+use std::time::{Duration, Instant};
+use serde::Serialize;
 use tokio::time::sleep;
 use crate::config::Config;
-use tracing::warn;
+use tracing::{Span, info, instrument, warn};
 
 // ======================= Types =======================
 
+/// Generates a unique ID for one worker/pipeline run
+///
+/// Attached as the `run_id` span field, which the Loki layer ships with
+/// every log line of the run so a whole pipeline run can be filtered.
+pub fn new_run_id() -> String {
+    uuid::Uuid::new_v4().simple().to_string()
+}
+
 /// Result type for fetch operations
 ///
-/// Represents three possible outcomes when fetching data from external APIs:
+/// Represents four possible outcomes when fetching data from external APIs:
 /// - Success: Data was fetched and parsed successfully
 /// - Empty: Request succeeded but returned empty data (e.g., empty array)
+/// - NotFound: The API answered HTTP 404 (not retried)
 /// - Failed: Request or parsing failed after all retries
 #[derive(Debug)]
 pub enum FetchResult<T> {
@@ -25,14 +42,458 @@ pub enum FetchResult<T> {
     Success(T),
     /// Request succeeded but returned empty data (e.g., "[]", "")
     Empty,
+    /// The resource does not exist (HTTP 404), e.g. a delisted CoinGecko ID
+    NotFound,
     /// Request or parsing failed with error message
     Failed(String),
 }
 
-// ======================= HTTP Utilities =======================
+// ======================= Circuit Breaker =======================
+
+/// Circuit breaker state
+#[derive(Debug, Clone, Copy, PartialEq, Eq, Serialize)]
+#[serde(rename_all = "snake_case")]
+pub enum CircuitState {
+    /// Requests flow normally
+    Closed,
+    /// Requests are short-circuited until the cooldown elapses
+    Open,
+    /// Cooldown elapsed, a single probe request is allowed through
+    /// (another one if it has not reported back within the cooldown)
+    HalfOpen,
+}
+
+/// Snapshot of a circuit breaker for status reporting
+#[derive(Debug, Clone, Serialize)]
+pub struct CircuitSnapshot {
+    pub state: CircuitState,
+    pub consecutive_failures: usize,
+    pub failure_threshold: usize,
+    pub cooldown_secs: u64,
+    /// Seconds left before an open breaker lets a probe through
+    pub retry_in_secs: Option<u64>,
+}
+
+#[derive(Debug)]
+struct BreakerInner {
+    state: CircuitState,
+    consecutive_failures: usize,
+    opened_at: Option<Instant>,
+    /// When the current half-open probe was let through
+    probe_started_at: Option<Instant>,
+}
+
+/// Per-API circuit breaker
+///
+/// Counts consecutive failed fetches against one upstream API. Once the
+/// count reaches `failure_threshold` the breaker opens and callers should
+/// skip the network entirely. After `cooldown` it half-opens and lets a
+/// single probe through: success closes it, failure re-opens it. A probe
+/// that never reports back (e.g. its future was dropped by a timeout) is
+/// replaced by a new one after another `cooldown`.
+#[derive(Debug)]
+pub struct CircuitBreaker {
+    /// API name used in logs (e.g., "coingecko")
+    name: String,
+    failure_threshold: usize,
+    cooldown: Duration,
+    inner: Mutex<BreakerInner>,
+}
+
+impl CircuitBreaker {
+    /// Creates a closed circuit breaker
+    ///
+    /// # Arguments
+    /// * `name` - API name used in logs
+    /// * `failure_threshold` - Consecutive failures before opening (min 1)
+    /// * `cooldown` - How long to stay open before probing again
+    pub fn new(name: &str, failure_threshold: usize, cooldown: Duration) -> Self {
+        CircuitBreaker {
+            name: name.to_string(),
+            failure_threshold: failure_threshold.max(1),
+            cooldown,
+            inner: Mutex::new(BreakerInner {
+                state: CircuitState::Closed,
+                consecutive_failures: 0,
+                opened_at: None,
+                probe_started_at: None,
+            }),
+        }
+    }
+
+    /// Returns whether a request may hit the network
+    ///
+    /// Transitions Open -> HalfOpen once the cooldown has elapsed; only
+    /// the first caller after the transition gets through as the probe.
+    /// While half-open, a new probe is let through once the previous one
+    /// has been outstanding for longer than the cooldown.
+    pub fn allow_request(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        match inner.state {
+            CircuitState::Closed => true,
+            CircuitState::HalfOpen => {
+                let probe_stale = inner
+                    .probe_started_at
+                    .map(|t| t.elapsed() > self.cooldown)
+                    .unwrap_or(true);
+                if probe_stale {
+                    inner.probe_started_at = Some(Instant::now());
+                    warn!("🟡 Circuit breaker '{}' probe did not report back, probing again", self.name);
+                }
+                probe_stale
+            }
+            CircuitState::Open => {
+                let cooled_down = inner
+                    .opened_at
+                    .map(|t| t.elapsed() >= self.cooldown)
+                    .unwrap_or(true);
+                if cooled_down {
+                    inner.state = CircuitState::HalfOpen;
+                    inner.probe_started_at = Some(Instant::now());
+                    info!("🟡 Circuit breaker '{}' half-open, probing", self.name);
+                }
+                cooled_down
+            }
+        }
+    }
+
+    /// Records a successful fetch, closing the breaker
+    pub fn record_success(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        if inner.state != CircuitState::Closed {
+            info!("🟢 Circuit breaker '{}' closed", self.name);
+        }
+        inner.state = CircuitState::Closed;
+        inner.consecutive_failures = 0;
+        inner.opened_at = None;
+        inner.probe_started_at = None;
+    }
+
+    /// Records a failed fetch, opening the breaker when the threshold is hit
+    pub fn record_failure(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.consecutive_failures += 1;
+        let should_open = inner.state == CircuitState::HalfOpen
+            || inner.consecutive_failures >= self.failure_threshold;
+        if should_open {
+            if inner.state != CircuitState::Open {
+                warn!(
+                    "🔴 Circuit breaker '{}' opened after {} consecutive failures, cooling down {}s",
+                    self.name,
+                    inner.consecutive_failures,
+                    self.cooldown.as_secs()
+                );
+            }
+            inner.state = CircuitState::Open;
+            inner.opened_at = Some(Instant::now());
+            inner.probe_started_at = None;
+        }
+    }
+
+    /// Returns a serializable snapshot for the status endpoint
+    pub fn snapshot(&self) -> CircuitSnapshot {
+        let inner = self.inner.lock().unwrap();
+        let retry_in_secs = match (inner.state, inner.opened_at) {
+            (CircuitState::Open, Some(t)) => Some(self.cooldown.saturating_sub(t.elapsed()).as_secs()),
+            _ => None,
+        };
+        CircuitSnapshot {
+            state: inner.state,
+            consecutive_failures: inner.consecutive_failures,
+            failure_threshold: self.failure_threshold,
+            cooldown_secs: self.cooldown.as_secs(),
+            retry_in_secs,
+        }
+    }
+}
+
+// ======================= Rate Limiter =======================
+
+/// Available share (percent) below which [`RateLimiter::acquire`] warns
+const RATE_LIMIT_WARN_PCT: f64 = 20.0;
+
+/// Tracked clients above which [`IpRateLimiter`] drops idle buckets
+const IP_RATE_LIMIT_PRUNE_THRESHOLD: usize = 10_000;
+
+/// Snapshot of a rate limiter for status reporting
+#[derive(Debug, Clone, Serialize)]
+pub struct RateLimiterSnapshot {
+    pub available_permits: usize,
+    pub max_tokens: usize,
+    /// Share of the budget in use, 0.0-1.0
+    pub utilization: f64,
+    /// Whether the next request has to wait for a refill
+    pub exhausted: bool,
+}
+
+/// Cumulative usage counters of a rate limiter (see [`RateLimiter::stats`])
+#[derive(Debug, Clone, Serialize, PartialEq)]
+pub struct RateLimiterStats {
+    /// Whole permits currently available
+    pub available: usize,
+    pub max: usize,
+    /// Share of the budget in use, 0-100
+    pub utilization_pct: f64,
+    /// Permits taken since startup
+    pub total_acquired: u64,
+    /// Acquisitions that found the bucket empty (waited or were rejected)
+    pub exhaustion_events: u64,
+    /// How long the most recent waiting acquisition waited
+    pub last_wait_ms: u64,
+}
+
+#[derive(Debug)]
+struct BucketInner {
+    /// Fractional tokens so slow refills are not lost to rounding
+    tokens: f64,
+    last_refill: Instant,
+    total_acquired: u64,
+    exhaustion_events: u64,
+    last_wait_ms: u64,
+}
+
+/// Token-bucket rate limiter for one upstream API
+///
+/// Holds up to `max_tokens` permits, refilled continuously at
+/// `max_tokens` per `period`. Each HTTP request takes one permit, or
+/// more for expensive endpoints (see [`acquire_weighted`](Self::acquire_weighted));
+/// callers wait when the bucket is empty.
+#[derive(Debug)]
+pub struct RateLimiter {
+    /// API name used in logs (e.g., "coingecko")
+    name: String,
+    max_tokens: usize,
+    period: Duration,
+    inner: Mutex<BucketInner>,
+}
+
+impl RateLimiter {
+    /// Creates a full bucket
+    ///
+    /// # Arguments
+    /// * `name` - API name used in logs
+    /// * `max_tokens` - Requests allowed per `period` (min 1)
+    /// * `period` - Window over which the bucket fully refills
+    pub fn new(name: &str, max_tokens: usize, period: Duration) -> Self {
+        let max_tokens = max_tokens.max(1);
+        RateLimiter {
+            name: name.to_string(),
+            max_tokens,
+            period,
+            inner: Mutex::new(BucketInner {
+                tokens: max_tokens as f64,
+                last_refill: Instant::now(),
+                total_acquired: 0,
+                exhaustion_events: 0,
+                last_wait_ms: 0,
+            }),
+        }
+    }
+
+    /// Adds the tokens earned since the last refill
+    fn refill(&self, inner: &mut BucketInner) {
+        let rate = self.max_tokens as f64 / self.period.as_secs_f64();
+        let earned = inner.last_refill.elapsed().as_secs_f64() * rate;
+        inner.tokens = (inner.tokens + earned).min(self.max_tokens as f64);
+        inner.last_refill = Instant::now();
+    }
+
+    /// Waits for and takes one permit
+    #[cfg(test)]
+    pub async fn acquire(&self) {
+        self.acquire_weighted(1).await
+    }
+
+    /// Waits for and takes `weight` permits at once
+    ///
+    /// The permits are taken together under the bucket lock, so concurrent
+    /// callers never split a weighted request. `weight` is capped at the
+    /// bucket size, which could otherwise never hold enough tokens.
+    pub async fn acquire_weighted(&self, weight: usize) {
+        let weight = weight.clamp(1, self.max_tokens);
+        let needed = weight as f64;
+        let mut waiting_since: Option<Instant> = None;
+        loop {
+            let wait = {
+                let mut inner = self.inner.lock().unwrap();
+                self.refill(&mut inner);
+                if inner.tokens >= needed {
+                    inner.tokens -= needed;
+                    inner.total_acquired += weight as u64;
+                    if let Some(since) = waiting_since {
+                        inner.last_wait_ms = since.elapsed().as_millis() as u64;
+                    }
+                    drop(inner);
+                    let pct = self.available_permits_pct();
+                    if pct < RATE_LIMIT_WARN_PCT {
+                        warn!(
+                            "⚠️ Rate limiter '{}' low: {:.0}% of {} permits left",
+                            self.name, pct, self.max_tokens
+                        );
+                    }
+                    return;
+                }
+                if waiting_since.is_none() {
+                    inner.exhaustion_events += 1;
+                    waiting_since = Some(Instant::now());
+                }
+                let rate = self.max_tokens as f64 / self.period.as_secs_f64();
+                Duration::from_secs_f64((needed - inner.tokens) / rate)
+            };
+            sleep(wait).await;
+        }
+    }
+
+    /// Takes one permit if available, without waiting
+    ///
+    /// # Returns
+    /// `false` if the bucket is empty
+    pub fn try_acquire(&self) -> bool {
+        let mut inner = self.inner.lock().unwrap();
+        self.refill(&mut inner);
+        if inner.tokens < 1.0 {
+            inner.exhaustion_events += 1;
+            return false;
+        }
+        inner.tokens -= 1.0;
+        inner.total_acquired += 1;
+        true
+    }
+
+    /// Refills the bucket and clears the counters so a test starts from a known state
+    #[cfg(test)]
+    pub fn reset(&self) {
+        let mut inner = self.inner.lock().unwrap();
+        inner.tokens = self.max_tokens as f64;
+        inner.last_refill = Instant::now();
+        inner.total_acquired = 0;
+        inner.exhaustion_events = 0;
+        inner.last_wait_ms = 0;
+    }
+
+    /// Whole permits currently available
+    pub fn available_permits(&self) -> usize {
+        let mut inner = self.inner.lock().unwrap();
+        self.refill(&mut inner);
+        inner.tokens as usize
+    }
+
+    /// Available permits as a percentage of the bucket size (0-100)
+    pub fn available_permits_pct(&self) -> f64 {
+        self.available_permits() as f64 / self.max_tokens as f64 * 100.0
+    }
+
+    /// Whether the next [`acquire`](Self::acquire) would have to wait
+    pub fn is_exhausted(&self) -> bool {
+        self.available_permits() == 0
+    }
+
+    /// Share of the budget in use (0.0 = idle, 1.0 = exhausted)
+    pub fn utilization(&self) -> f64 {
+        (self.max_tokens - self.available_permits()) as f64 / self.max_tokens as f64
+    }
+
+    /// Returns the current budget together with the cumulative usage counters
+    pub fn stats(&self) -> RateLimiterStats {
+        let mut inner = self.inner.lock().unwrap();
+        self.refill(&mut inner);
+        let available = inner.tokens as usize;
+        RateLimiterStats {
+            available,
+            max: self.max_tokens,
+            utilization_pct: (self.max_tokens - available) as f64 / self.max_tokens as f64 * 100.0,
+            total_acquired: inner.total_acquired,
+            exhaustion_events: inner.exhaustion_events,
+            last_wait_ms: inner.last_wait_ms,
+        }
+    }
+
+    /// Returns a serializable snapshot for the status endpoint
+    pub fn snapshot(&self) -> RateLimiterSnapshot {
+        RateLimiterSnapshot {
+            available_permits: self.available_permits(),
+            max_tokens: self.max_tokens,
+            utilization: self.utilization(),
+            exhausted: self.is_exhausted(),
+        }
+    }
+}
+
+/// Rate limiter with one token bucket per client IP
+///
+/// Used for inbound requests: a client over its budget is rejected
+/// (see [`try_acquire`](Self::try_acquire)) instead of made to wait.
+#[derive(Debug)]
+pub struct IpRateLimiter {
+    /// Endpoint name used in logs (e.g., "manager")
+    name: String,
+    max_tokens: usize,
+    period: Duration,
+    buckets: Mutex<HashMap<IpAddr, RateLimiter>>,
+}
+
+impl IpRateLimiter {
+    /// Creates a limiter allowing `max_tokens` requests per `period` per IP
+    pub fn new(name: &str, max_tokens: usize, period: Duration) -> Self {
+        IpRateLimiter {
+            name: name.to_string(),
+            max_tokens,
+            period,
+            buckets: Mutex::new(HashMap::new()),
+        }
+    }
+
+    /// Takes one permit from `ip`'s bucket
+    ///
+    /// Once `IP_RATE_LIMIT_PRUNE_THRESHOLD` clients are tracked, buckets
+    /// that have fully refilled are dropped before a new one is added.
+    ///
+    /// # Returns
+    /// `false` if `ip` has used up its budget
+    pub fn try_acquire(&self, ip: IpAddr) -> bool {
+        let mut buckets = self.buckets.lock().unwrap();
+        if buckets.len() >= IP_RATE_LIMIT_PRUNE_THRESHOLD && !buckets.contains_key(&ip) {
+            buckets.retain(|_, bucket| bucket.available_permits() < bucket.max_tokens);
+        }
+        let allowed = buckets
+            .entry(ip)
+            .or_insert_with(|| RateLimiter::new(&self.name, self.max_tokens, self.period))
+            .try_acquire();
+        if !allowed {
+            warn!("⚠️ Rate limit '{}' exceeded by {}", self.name, ip);
+        }
+        allowed
+    }
+}
 
 // ======================= HTTP Utilities =======================
 
+/// Retry policy of [`get_json_with_retry`]
+///
+/// Usually built by [`Config::retry_config`], with per-call overrides via
+/// struct update syntax.
+#[derive(Debug, Clone, Copy, PartialEq, Eq)]
+pub struct RetryConfig {
+    /// Maximum number of attempts (total attempts, not retries)
+    pub max_retry: usize,
+    /// Consecutive failures after which the call gives up early
+    pub max_consecutive_fail: usize,
+    /// Delay after the first failed attempt, growing linearly per attempt
+    pub backoff_base_ms: u64,
+    /// Upper bound of the delay between attempts
+    pub backoff_max_ms: u64,
+    /// Rate limiter permits each attempt takes (1 unless the endpoint is expensive)
+    pub weight: usize,
+}
+
+impl RetryConfig {
+    /// Delay before the attempt following `attempt` (1-based)
+    pub fn backoff(&self, attempt: usize) -> Duration {
+        let delay_ms = self.backoff_base_ms.saturating_mul(attempt as u64);
+        Duration::from_millis(delay_ms.min(self.backoff_max_ms))
+    }
+}
+
 /// Fetches and parses JSON data with automatic retry logic
 ///
 /// This function provides robust HTTP request handling with:
@@ -49,18 +510,21 @@ pub enum FetchResult<T> {
 /// * `config` - Application configuration (provides HTTP client)
 /// * `url` - Target URL to fetch from
 /// * `headers` - Function to add custom headers to the request
-/// * `max_retry` - Maximum number of retry attempts (total attempts, not retries)
-/// * `max_consecutive_fail` - Maximum consecutive failures before giving up (circuit breaker)
+/// * `retry` - Attempt count, early give-up threshold, backoff bounds and permit weight
+/// * `breaker` - Optional per-API circuit breaker; when open, returns `Failed` without a request
+/// * `limiter` - Optional per-API rate limiter; every attempt waits for `retry.weight` permits
 ///
 /// # Returns
 /// * `FetchResult::Success(T)` - Successfully fetched and parsed data
 /// * `FetchResult::Empty` - Response was empty ("" or "[]")
+/// * `FetchResult::NotFound` - HTTP 404 (returned on the first attempt)
 /// * `FetchResult::Failed(String)` - Failed after retries with error message
 ///
 /// # Retry Strategy
-/// - Exponential backoff: 300ms × attempt_number
+/// - Backoff: `backoff_base_ms` × attempt_number, capped at `backoff_max_ms`
 /// - Circuit breaker: Stops if consecutive failures reach threshold
 /// - Last attempt: No sleep delay after final failure
+/// - Shared breaker: A final `Failed` counts against `breaker`; `Success`/`Empty` resets it
 ///
 /// # Example
 /// ```no_run
@@ -70,26 +534,86 @@ pub enum FetchResult<T> {
 ///     &config,
 ///     "https://api.example.com/data",
 ///     |req| req.header("Authorization", "Bearer token"),
-///     5,  // max 5 attempts
-///     3,  // stop after 3 consecutive failures
+///     config.retry_config(),
+///     Some(&config.coingecko_breaker),
+///     Some(&config.coingecko_rate_limiter),
 /// ).await;
 /// ```
 pub async fn get_json_with_retry<T: serde::de::DeserializeOwned>(
     config: &Config,
     url: &str,
     headers: impl Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
-    max_retry: usize,
-    max_consecutive_fail: usize,
+    retry: RetryConfig,
+    breaker: Option<&CircuitBreaker>,
+    limiter: Option<&RateLimiter>,
+) -> FetchResult<T> {
+    get_json_with_retry_with(config, url, headers, retry, breaker, limiter, |text| serde_json::from_str(text)).await
+}
+
+/// Like [`get_json_with_retry`], but parses the body with `parse`
+///
+/// Lets callers deserialize with a [`serde::de::DeserializeSeed`], e.g. to
+/// drop unwanted elements of a large array while parsing instead of
+/// collecting them all first. `parse` runs once per successful response.
+pub async fn get_json_with_retry_with<T>(
+    config: &Config,
+    url: &str,
+    headers: impl Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+    retry: RetryConfig,
+    breaker: Option<&CircuitBreaker>,
+    limiter: Option<&RateLimiter>,
+    parse: impl Fn(&str) -> serde_json::Result<T>,
+) -> FetchResult<T> {
+    // Short-circuit while the API's breaker is open
+    if let Some(breaker) = breaker
+        && !breaker.allow_request()
+    {
+        return FetchResult::Failed(format!("Circuit open, skipping {}", url));
+    }
+
+    let result = fetch_json_with_retry(config, url, headers, retry, limiter, parse).await;
+
+    if let Some(breaker) = breaker {
+        match result {
+            FetchResult::Failed(_) => breaker.record_failure(),
+            _ => breaker.record_success(),
+        }
+    }
+    result
+}
+
+/// Retry loop behind [`get_json_with_retry`], without breaker bookkeeping
+///
+/// Runs in an `http_request` span carrying the URL and current attempt.
+#[instrument(name = "http_request", skip_all, fields(url = %url, attempt = tracing::field::Empty))]
+async fn fetch_json_with_retry<T>(
+    config: &Config,
+    url: &str,
+    headers: impl Fn(reqwest::RequestBuilder) -> reqwest::RequestBuilder,
+    retry: RetryConfig,
+    limiter: Option<&RateLimiter>,
+    parse: impl Fn(&str) -> serde_json::Result<T>,
 ) -> FetchResult<T> {
+    let RetryConfig { max_retry, max_consecutive_fail, weight, .. } = retry;
     // Track consecutive failures for circuit breaker pattern
     let mut consecutive_fail = 0;
 
     for attempt in 1..=max_retry {
+        Span::current().record("attempt", attempt);
+        if let Some(limiter) = limiter {
+            limiter.acquire_weighted(weight).await;
+        }
+
         // Build and send HTTP request with custom headers
-        let req = headers(config.http_client.get(url));
+        let req = headers(config.http_get(url));
         
         match req.send().await {
             Ok(resp) => {
+                // A missing resource stays missing: report it without retrying
+                if resp.status() == reqwest::StatusCode::NOT_FOUND {
+                    return FetchResult::NotFound;
+                }
+
                 // Check HTTP status code
                 match resp.error_for_status() {
                     Ok(resp_ok) => {
@@ -102,7 +626,7 @@ pub async fn get_json_with_retry<T: serde::de::DeserializeOwned>(
                                 }
                                 
                                 // Parse JSON
-                                match serde_json::from_str::<T>(&text) {
+                                match parse(&text) {
                                     Ok(parsed) => {
                                         // Success! Return immediately
                                         return FetchResult::Success(parsed);
@@ -151,9 +675,9 @@ pub async fn get_json_with_retry<T: serde::de::DeserializeOwned>(
             ));
         }
 
-        // Exponential backoff, but skip sleep on last attempt
+        // Back off, but skip sleep on last attempt
         if attempt < max_retry {
-            sleep(Duration::from_millis(300 * attempt as u64)).await;
+            sleep(retry.backoff(attempt)).await;
         }
     }
 
@@ -164,8 +688,148 @@ pub async fn get_json_with_retry<T: serde::de::DeserializeOwned>(
     ))
 }
 
+// ======================= Database Utilities =======================
+
+/// Attempts of a [`retry_db`] operation used by the workers
+pub const DB_RETRY_ATTEMPTS: usize = 3;
+
+/// Delay after the first failed [`retry_db`] attempt, growing linearly per attempt
+pub const DB_RETRY_BACKOFF: Duration = Duration::from_secs(2);
+
+/// Returns whether a database error is worth retrying
+///
+/// Lost connections (I/O errors, Postgres restarting or shutting down,
+/// pool exhaustion) are transient; the pool opens a fresh connection on the
+/// next attempt. Query errors such as constraint violations or bad SQL
+/// would fail the same way again.
+pub fn is_transient_db_error(e: &sqlx::Error) -> bool {
+    match e {
+        sqlx::Error::Io(_)
+        | sqlx::Error::PoolTimedOut
+        | sqlx::Error::PoolClosed
+        | sqlx::Error::WorkerCrashed => true,
+        sqlx::Error::Database(db) => db.code().is_some_and(|code| {
+            // Class 08: connection exception; 57P01-03: admin/crash shutdown, starting up
+            code.starts_with("08") || matches!(code.as_ref(), "57P01" | "57P02" | "57P03")
+        }),
+        _ => false,
+    }
+}
+
+/// Runs a database operation, retrying it on transient connection errors
+///
+/// `op` is called again from scratch on each attempt, so it must be safe to
+/// repeat: open its own transaction rather than reuse one from the caller.
+/// Errors that do not wrap a transient [`sqlx::Error`] are returned at once.
+///
+/// # Arguments
+/// * `label` - Operation name for logs
+/// * `attempts` - Maximum number of attempts (total attempts, not retries)
+/// * `backoff` - Delay after the first failure, multiplied by the attempt number
+/// * `op` - Operation to run
+pub async fn retry_db<T, F, Fut>(label: &str, attempts: usize, backoff: Duration, mut op: F) -> anyhow::Result<T>
+where
+    F: FnMut() -> Fut,
+    Fut: std::future::Future<Output = anyhow::Result<T>>,
+{
+    let mut attempt = 1;
+    loop {
+        match op().await {
+            Ok(value) => return Ok(value),
+            Err(e) if attempt < attempts && is_transient_anyhow(&e) => {
+                warn!("⚠️ {} lost its database connection (attempt {}/{}): {:#}", label, attempt, attempts, e);
+                sleep(backoff * attempt as u32).await;
+                attempt += 1;
+            }
+            Err(e) => return Err(e),
+        }
+    }
+}
+
+/// Returns whether any error in the chain is a transient [`sqlx::Error`]
+fn is_transient_anyhow(e: &anyhow::Error) -> bool {
+    e.chain()
+        .filter_map(|cause| cause.downcast_ref::<sqlx::Error>())
+        .any(is_transient_db_error)
+}
+
+// ======================= Address Utilities =======================
+
+/// Validates a `0x` EVM address and returns it lowercased, the form stored in the database
+///
+/// Mixed-case input must carry a valid EIP-55 checksum, see
+/// [`validate_eth_address_checksum`].
+pub fn validate_eth_address(addr: &str) -> anyhow::Result<String> {
+    validate_eth_address_checksum(addr)?;
+    Ok(addr.to_lowercase())
+}
+
+/// Validates a `0x` EVM address and returns its EIP-55 checksummed form
+///
+/// All-lowercase or all-uppercase input carries no checksum and is accepted;
+/// mixed-case input must match the keccak256-based checksum, which catches
+/// mistyped characters.
+///
+/// # Returns
+/// * `Ok(String)` - Checksummed address (e.g., `0xB4e16d01...`)
+/// * `Err(anyhow::Error)` - Not a `0x`-prefixed 20-byte hex address, or a wrong checksum
+pub fn validate_eth_address_checksum(addr: &str) -> anyhow::Result<String> {
+    let hex = addr
+        .strip_prefix("0x")
+        .ok_or_else(|| anyhow::anyhow!("Invalid address (missing 0x prefix): {}", addr))?;
+    let address = addr
+        .parse::<alloy::primitives::Address>()
+        .map_err(|_| anyhow::anyhow!("Invalid address: {}", addr))?;
+
+    let checksummed = address.to_checksum(None);
+    let mixed_case = hex.bytes().any(|b| b.is_ascii_lowercase()) && hex.bytes().any(|b| b.is_ascii_uppercase());
+    if mixed_case && checksummed[2..] != *hex {
+        anyhow::bail!("Invalid EIP-55 checksum: {} (expected {})", addr, checksummed);
+    }
+    Ok(checksummed)
+}
+
+// ======================= Alerts =======================
+
+/// Posts an operator alert to the configured webhook
+///
+/// The payload is `{"text": message}`, which Slack-style incoming webhooks
+/// accept as-is. Does nothing without a webhook; delivery failures are only
+/// logged, since alerts must never break the caller.
+///
+/// # Arguments
+/// * `client` - Shared HTTP client
+/// * `webhook_url` - `ALERT_WEBHOOK_URL`, if configured
+/// * `message` - Alert text
+pub async fn send_alert(client: &reqwest::Client, webhook_url: Option<&str>, message: &str) {
+    let Some(url) = webhook_url else {
+        return;
+    };
+
+    let result = client
+        .post(url)
+        .json(&serde_json::json!({"text": message}))
+        .send()
+        .await
+        .and_then(|resp| resp.error_for_status());
+
+    if let Err(e) = result {
+        warn!("⚠️ Failed to deliver alert webhook: {}", e);
+    }
+}
+
 // ======================= Tests =======================
 
+/// Serves `body` as JSON at `path` on a local port, returning the base URL
+#[cfg(test)]
+pub(crate) async fn serve_json(path: &'static str, body: serde_json::Value) -> String {
+    let listener = tokio::net::TcpListener::bind("127.0.0.1:0").await.unwrap();
+    let base_url = format!("http://{}", listener.local_addr().unwrap());
+    let app = axum::Router::new().route(path, axum::routing::get(move || async move { axum::Json(body) }));
+    tokio::spawn(async move { axum::serve(listener, app).await.unwrap() });
+    base_url
+}
+
 #[cfg(test)]
 mod tests {
     use super::*;
@@ -177,6 +841,45 @@ mod tests {
         name: String,
     }
 
+    /// Test which database errors count as transient
+    #[test]
+    fn test_is_transient_db_error() {
+        let io = std::io::Error::new(std::io::ErrorKind::ConnectionReset, "reset");
+        assert!(is_transient_db_error(&sqlx::Error::Io(io)));
+        assert!(is_transient_db_error(&sqlx::Error::PoolClosed));
+        assert!(is_transient_db_error(&sqlx::Error::PoolTimedOut));
+        assert!(!is_transient_db_error(&sqlx::Error::RowNotFound));
+        assert!(!is_transient_db_error(&sqlx::Error::ColumnNotFound("id".to_string())));
+    }
+
+    /// Test that retry_db only retries transient errors
+    #[tokio::test]
+    async fn test_retry_db_retries_transient_errors_only() {
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let result = retry_db("test", 3, Duration::from_millis(1), || async {
+            match calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst) {
+                0 => Err(anyhow::Error::from(sqlx::Error::PoolTimedOut).context("insert failed")),
+                _ => Ok(42),
+            }
+        })
+        .await;
+        assert_eq!(result.unwrap(), 42);
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 2);
+
+        let calls = std::sync::atomic::AtomicUsize::new(0);
+        let result: anyhow::Result<()> = retry_db("test", 3, Duration::from_millis(1), || async {
+            calls.fetch_add(1, std::sync::atomic::Ordering::SeqCst);
+            Err(sqlx::Error::RowNotFound.into())
+        })
+        .await;
+        assert!(result.is_err());
+        assert_eq!(calls.load(std::sync::atomic::Ordering::SeqCst), 1);
+
+        let result: anyhow::Result<()> =
+            retry_db("test", 3, Duration::from_millis(1), || async { Err(sqlx::Error::PoolClosed.into()) }).await;
+        assert!(result.is_err(), "gives up after the last attempt");
+    }
+
     /// Test FetchResult enum variants
     #[test]
     fn test_fetch_result_variants() {
@@ -287,6 +990,17 @@ mod tests {
         assert!(result.is_err(), "Should fail to parse invalid JSON");
     }
 
+    /// Test that the retry backoff is capped
+    #[test]
+    fn test_retry_backoff_is_capped() {
+        let retry =
+            RetryConfig { max_retry: 10, max_consecutive_fail: 10, backoff_base_ms: 300, backoff_max_ms: 1_000, weight: 1 };
+        assert_eq!(retry.backoff(1), Duration::from_millis(300));
+        assert_eq!(retry.backoff(3), Duration::from_millis(900));
+        assert_eq!(retry.backoff(4), Duration::from_millis(1_000));
+        assert_eq!(retry.backoff(usize::MAX), Duration::from_millis(1_000));
+    }
+
     /// Test max retry calculation
     #[test]
     fn test_max_retry_range() {
@@ -298,6 +1012,223 @@ mod tests {
         assert_eq!(attempts[max_retry - 1], max_retry);
     }
 
+    /// Test that the breaker opens after the failure threshold
+    #[test]
+    fn test_circuit_breaker_opens_after_threshold() {
+        let breaker = CircuitBreaker::new("test", 3, Duration::from_secs(60));
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+
+        breaker.record_failure();
+        breaker.record_failure();
+        assert!(breaker.allow_request(), "Should stay closed below threshold");
+
+        breaker.record_failure();
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+        assert!(!breaker.allow_request(), "Open breaker should short-circuit");
+    }
+
+    /// Test that a success resets the failure count
+    #[test]
+    fn test_circuit_breaker_success_resets() {
+        let breaker = CircuitBreaker::new("test", 2, Duration::from_secs(60));
+        breaker.record_failure();
+        breaker.record_success();
+        breaker.record_failure();
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+        assert_eq!(breaker.snapshot().consecutive_failures, 1);
+    }
+
+    /// Test half-open probing after the cooldown
+    #[test]
+    fn test_circuit_breaker_half_open_probe() {
+        let cooldown = Duration::from_millis(50);
+        let breaker = CircuitBreaker::new("test", 1, cooldown);
+        breaker.record_failure();
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+
+        // Cooldown elapsed: first caller probes, others still blocked
+        std::thread::sleep(cooldown);
+        assert!(breaker.allow_request());
+        assert_eq!(breaker.snapshot().state, CircuitState::HalfOpen);
+        assert!(!breaker.allow_request(), "Only one probe should pass");
+
+        // Failed probe re-opens, successful probe closes
+        breaker.record_failure();
+        assert_eq!(breaker.snapshot().state, CircuitState::Open);
+        std::thread::sleep(cooldown);
+        assert!(breaker.allow_request());
+        breaker.record_success();
+        assert_eq!(breaker.snapshot().state, CircuitState::Closed);
+    }
+
+    /// Test that a probe which never reports back is replaced after the cooldown
+    #[test]
+    fn test_circuit_breaker_stale_probe() {
+        let cooldown = Duration::from_millis(50);
+        let breaker = CircuitBreaker::new("test", 1, cooldown);
+        breaker.record_failure();
+        std::thread::sleep(cooldown);
+        assert!(breaker.allow_request(), "First probe should pass");
+
+        // The probe is dropped without recording a result
+        assert!(!breaker.allow_request());
+        std::thread::sleep(cooldown * 2);
+        assert!(breaker.allow_request(), "Stale probe should be replaced");
+        assert!(!breaker.allow_request(), "Only one replacement probe should pass");
+        assert_eq!(breaker.snapshot().state, CircuitState::HalfOpen);
+    }
+
+    /// Test that run IDs are unique and compact
+    #[test]
+    fn test_new_run_id() {
+        let a = new_run_id();
+        assert_eq!(a.len(), 32);
+        assert_ne!(a, new_run_id());
+    }
+
+    /// Test permit accounting helpers of the rate limiter
+    #[tokio::test]
+    async fn test_rate_limiter_utilization() {
+        let limiter = RateLimiter::new("test", 4, Duration::from_secs(3600));
+        assert_eq!(limiter.available_permits(), 4);
+        assert_eq!(limiter.available_permits_pct(), 100.0);
+        assert_eq!(limiter.utilization(), 0.0);
+
+        limiter.acquire().await;
+        assert_eq!(limiter.available_permits_pct(), 75.0);
+        assert_eq!(limiter.utilization(), 0.25);
+
+        for _ in 0..3 {
+            limiter.acquire().await;
+        }
+        assert!(limiter.is_exhausted());
+        assert_eq!(limiter.utilization(), 1.0);
+    }
+
+    /// Test that acquire waits for a refill once the bucket is empty
+    #[tokio::test]
+    async fn test_rate_limiter_waits_for_refill() {
+        let limiter = RateLimiter::new("test", 1, Duration::from_millis(100));
+        limiter.acquire().await;
+
+        let start = Instant::now();
+        limiter.acquire().await;
+        assert!(start.elapsed() >= Duration::from_millis(80));
+    }
+
+    /// Test that a weighted acquire takes all its permits at once
+    #[tokio::test]
+    async fn test_rate_limiter_acquire_weighted() {
+        let limiter = RateLimiter::new("test", 10, Duration::from_secs(3600));
+        limiter.acquire_weighted(10).await;
+        assert_eq!(limiter.available_permits(), 0);
+        assert!(limiter.is_exhausted());
+    }
+
+    /// Test the cumulative counters behind get_rate_limiter_status
+    #[tokio::test]
+    async fn test_rate_limiter_stats() {
+        // An hour-long period refills nothing while the test runs
+        let limiter = RateLimiter::new("test", 2, Duration::from_secs(3600));
+        limiter.acquire().await;
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+
+        let stats = limiter.stats();
+        assert_eq!(stats.available, 0);
+        assert_eq!(stats.max, 2);
+        assert_eq!(stats.utilization_pct, 100.0);
+        assert_eq!(stats.total_acquired, 2);
+        assert_eq!(stats.exhaustion_events, 1);
+        assert_eq!(stats.last_wait_ms, 0);
+    }
+
+    /// Test that acquire on an empty bucket waits for a refill and records the wait
+    #[tokio::test]
+    async fn test_rate_limiter_wait() {
+        // One permit every 100ms
+        let limiter = RateLimiter::new("test", 2, Duration::from_millis(200));
+        limiter.acquire().await;
+        limiter.acquire().await;
+
+        let start = Instant::now();
+        limiter.acquire().await;
+        let waited = start.elapsed();
+
+        // Lower bounds only: the refill can't come sooner, but a busy machine may be slower
+        assert!(waited >= Duration::from_millis(80), "waited {:?}", waited);
+        let stats = limiter.stats();
+        assert_eq!(stats.total_acquired, 3);
+        assert_eq!(stats.exhaustion_events, 1);
+        assert!(stats.last_wait_ms >= 80, "last_wait_ms {}", stats.last_wait_ms);
+    }
+
+    /// Test that try_acquire rejects instead of waiting once the bucket is empty
+    #[test]
+    fn test_rate_limiter_try_acquire() {
+        let limiter = RateLimiter::new("test", 2, Duration::from_secs(3600));
+        assert!(limiter.try_acquire());
+        assert!(limiter.try_acquire());
+        assert!(!limiter.try_acquire());
+    }
+
+    /// Test that reset restores the full budget of a drained limiter
+    #[tokio::test]
+    async fn test_rate_limiter_reset() {
+        let limiter = RateLimiter::new("test", 2, Duration::from_millis(200));
+        limiter.acquire().await;
+        limiter.acquire().await;
+        // The bucket is empty, so this one waits for a refill
+        limiter.acquire().await;
+        let stats = limiter.stats();
+        assert_eq!(stats.total_acquired, 3);
+        assert_eq!(stats.exhaustion_events, 1);
+        assert!(stats.last_wait_ms > 0);
+
+        limiter.reset();
+        assert_eq!(limiter.available_permits(), 2);
+        assert_eq!(limiter.utilization(), 0.0);
+        let stats = limiter.stats();
+        assert_eq!(stats.total_acquired, 0);
+        assert_eq!(stats.exhaustion_events, 0);
+        assert_eq!(stats.last_wait_ms, 0);
+    }
+
+    /// Test that a fetch takes its permit from the CoinGecko limiter
+    #[tokio::test]
+    async fn test_get_json_with_retry_uses_rate_limiter() {
+        let config = Config::for_tests();
+        config.coingecko_rate_limiter.reset();
+        let base_url = serve_json("/data", serde_json::json!({"id": 1, "name": "test"})).await;
+
+        let result = get_json_with_retry::<TestData>(
+            &config,
+            &format!("{}/data", base_url),
+            |r| r,
+            config.retry_config(),
+            None,
+            Some(&config.coingecko_rate_limiter),
+        )
+        .await;
+
+        assert!(matches!(result, FetchResult::Success(TestData { id: 1, .. })));
+        let stats = config.coingecko_rate_limiter.stats();
+        assert_eq!(stats.total_acquired, 1);
+        assert_eq!(stats.exhaustion_events, 0);
+    }
+
+    /// Test that each IP gets its own budget
+    #[test]
+    fn test_ip_rate_limiter_per_ip() {
+        let limiter = IpRateLimiter::new("test", 1, Duration::from_secs(3600));
+        let a: IpAddr = "10.0.0.1".parse().unwrap();
+        let b: IpAddr = "10.0.0.2".parse().unwrap();
+
+        assert!(limiter.try_acquire(a));
+        assert!(!limiter.try_acquire(a));
+        assert!(limiter.try_acquire(b));
+    }
+
     /// Test that last attempt doesn't need sleep
     #[test]
     fn test_last_attempt_no_sleep() {
@@ -313,4 +1244,35 @@ mod tests {
             }
         }
     }
+
+    /// Test EIP-55 checksum validation against known and mistyped addresses
+    #[test]
+    fn test_validate_eth_address_checksum() {
+        // EIP-55 reference vectors
+        for addr in ["0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed", "0xfB6916095ca1df60bB79Ce92cE3Ea74c37c5d359"] {
+            assert_eq!(validate_eth_address_checksum(addr).unwrap(), addr);
+        }
+        // No checksum to verify: returned checksummed
+        assert_eq!(
+            validate_eth_address_checksum("0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc").unwrap(),
+            "0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc"
+        );
+        // One character with the wrong case
+        let err = validate_eth_address_checksum("0xb4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc").unwrap_err();
+        assert!(err.to_string().contains("expected 0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc"));
+        // One mistyped hex digit
+        assert!(validate_eth_address_checksum("0x5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAee").is_err());
+        assert!(validate_eth_address_checksum("5aAeb6053F3E94C9b9A09f33669435E7Ef1BeAed").is_err());
+        assert!(validate_eth_address_checksum("0x1234").is_err());
+    }
+
+    /// Test that the storage form is lowercase
+    #[test]
+    fn test_validate_eth_address() {
+        assert_eq!(
+            validate_eth_address("0xB4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc").unwrap(),
+            "0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc"
+        );
+        assert!(validate_eth_address("0xb4e16d0168e52d35CaCD2c6185b44281Ec28C9Dc").is_err());
+    }
 }
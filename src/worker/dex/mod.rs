@@ -0,0 +1,5 @@
+//! DEX event indexing
+//!
+//! Indexes on-chain swap events of configured pools over JSON-RPC.
+
+pub mod uniswapv2;
@@ -0,0 +1,248 @@
+//! UniswapV2 Swap event indexing
+//!
+//! Polls `eth_getLogs` for `Swap` events of the pairs in `UNISWAP_PAIRS`
+//! and stores them in `swaps`, with `token0`/`token1` resolved from
+//! `pair_tokens` (populated separately).
+
+use crate::config::Config;
+use crate::utils::{DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, retry_db};
+use crate::worker::SyncStats;
+use alloy::eips::BlockNumberOrTag;
+use alloy::primitives::Address;
+use alloy::providers::{Provider, ProviderBuilder};
+use alloy::rpc::types::{Filter, Log};
+use alloy::sol;
+use alloy::sol_types::SolEvent;
+use anyhow::{Context, Result};
+use sqlx::PgPool;
+use std::collections::HashMap;
+use tracing::{info, instrument, warn};
+
+/// Blocks behind the head left unindexed to stay clear of reorgs
+const CONFIRMATIONS: u64 = 12;
+/// Maximum block span per `eth_getLogs` request
+const MAX_BLOCK_RANGE: u64 = 2_000;
+/// Blocks scanned back from the head when nothing was indexed yet
+const INITIAL_LOOKBACK_BLOCKS: u64 = 1_000;
+
+sol! {
+    /// UniswapV2 pair contract events
+    interface IUniswapV2Pair {
+        event Swap(
+            address indexed sender,
+            uint amount0In,
+            uint amount1In,
+            uint amount0Out,
+            uint amount1Out,
+            address indexed to
+        );
+    }
+}
+
+/// UniswapV2 `Swap` event
+///
+/// Declared inside `IUniswapV2Pair` so the event keeps its on-chain name
+/// (and thus its topic hash).
+pub use IUniswapV2Pair::Swap as UniswapV2Swap;
+
+/// Indexes `Swap` events of the configured pairs up to the confirmed head
+///
+/// # Workflow
+/// 1. Start at `from_block`, or resume after the highest indexed block,
+///    or scan the last `INITIAL_LOOKBACK_BLOCKS` on a fresh table
+/// 2. Fetch logs in windows of `MAX_BLOCK_RANGE` up to head - `CONFIRMATIONS`
+/// 3. Insert each event, skipping ones already stored
+///
+/// A failed insert aborts the run so the caller scans the same range again
+/// (already stored events are skipped), instead of moving past lost swaps.
+///
+/// # Arguments
+/// * `config` - Application configuration (`DEX_RPC_URL`, `UNISWAP_PAIRS`, `DEX_CHAINID`)
+/// * `from_block` - First block to scan (the previous run's return value)
+///
+/// # Returns
+/// * `Ok((stats, next_block))` - `next_block` is where the next run should start
+/// * `Err(anyhow::Error)` - RPC or database error
+#[instrument(skip_all)]
+pub async fn index_uniswapv2_swaps(config: &Config, from_block: Option<u64>) -> Result<(SyncStats, u64)> {
+    let rpc_url = config.dex_rpc_url.as_deref().context("DEX_RPC_URL is not set")?;
+    let pairs = config
+        .uniswap_pairs
+        .iter()
+        .map(|p| p.parse::<Address>().with_context(|| format!("Invalid pair address: {}", p)))
+        .collect::<Result<Vec<_>>>()?;
+
+    let provider = ProviderBuilder::new().connect_http(rpc_url.parse().context("Invalid DEX_RPC_URL")?);
+    let pool = &config.postgres_db.pool;
+    let chainid = config.dex_chainid;
+
+    let head = provider.get_block_number().await.context("Failed to fetch block number")?;
+    let safe_head = head.saturating_sub(CONFIRMATIONS);
+
+    let mut start = match from_block {
+        Some(block) => block,
+        None => match last_indexed_block(pool, chainid, &config.uniswap_pairs).await? {
+            Some(block) => block + 1,
+            None => safe_head.saturating_sub(INITIAL_LOOKBACK_BLOCKS),
+        },
+    };
+
+    let mut stats = SyncStats::default();
+    let mut timestamps: HashMap<u64, Option<i64>> = HashMap::new();
+
+    while start <= safe_head {
+        let end = (start + MAX_BLOCK_RANGE - 1).min(safe_head);
+        let filter = Filter::new()
+            .address(pairs.clone())
+            .event_signature(UniswapV2Swap::SIGNATURE_HASH)
+            .from_block(start)
+            .to_block(end);
+
+        let logs = provider
+            .get_logs(&filter)
+            .await
+            .with_context(|| format!("Failed to fetch logs for blocks {}-{}", start, end))?;
+        stats.pages += 1;
+
+        for log in &logs {
+            let Some(block_number) = log.block_number else {
+                continue; // Pending log
+            };
+            let timestamp = match log.block_timestamp {
+                Some(ts) => Some(ts as i64),
+                None => match timestamps.get(&block_number) {
+                    Some(ts) => *ts,
+                    None => {
+                        let ts = provider
+                            .get_block_by_number(BlockNumberOrTag::Number(block_number))
+                            .await?
+                            .map(|block| block.header.timestamp as i64);
+                        timestamps.insert(block_number, ts);
+                        ts
+                    }
+                },
+            };
+
+            let swap = match log.log_decode::<UniswapV2Swap>() {
+                Ok(decoded) => decoded.inner.data,
+                Err(e) => {
+                    warn!("⚠️ Skipping undecodable swap log in block {}: {}", block_number, e);
+                    continue;
+                }
+            };
+            let inserted = retry_db("swap insert", DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, || {
+                insert_swap(pool, chainid, log, &swap, timestamp)
+            })
+            .await
+            .with_context(|| format!("Failed to store swap in block {}", block_number))?;
+            if inserted {
+                stats.inserted += 1;
+            }
+        }
+
+        start = end + 1;
+    }
+
+    if stats.inserted > 0 {
+        info!("🦄 Indexed {} UniswapV2 swap(s) up to block {}", stats.inserted, safe_head);
+    }
+    Ok((stats, start))
+}
+
+/// Returns the highest block with a stored swap for the given pairs
+async fn last_indexed_block(pool: &PgPool, chainid: i64, pairs: &[String]) -> Result<Option<u64>> {
+    let block: Option<i64> =
+        sqlx::query_scalar("SELECT MAX(block_number) FROM swaps WHERE chainid = $1 AND pair_address = ANY($2)")
+            .bind(chainid)
+            .bind(pairs)
+            .fetch_one(pool)
+            .await?;
+    Ok(block.map(|b| b as u64))
+}
+
+/// Stores one decoded `Swap` log
+///
+/// # Returns
+/// * `Ok(true)` - Swap inserted
+/// * `Ok(false)` - Already stored (re-scanned block)
+/// * `Err` - Log without position fields, or database error
+async fn insert_swap(
+    pool: &PgPool,
+    chainid: i64,
+    log: &Log,
+    swap: &UniswapV2Swap,
+    timestamp: Option<i64>,
+) -> Result<bool> {
+    let tx_hash = log.transaction_hash.context("Log without transaction hash")?;
+    let log_index = log.log_index.context("Log without log index")?;
+    let block_number = log.block_number.context("Log without block number")?;
+
+    let result = sqlx::query(
+        r#"
+        INSERT INTO swaps (
+            chainid, pair_address, sender, to_address,
+            amount0_in, amount1_in, amount0_out, amount1_out,
+            token0, token1, block_number, tx_hash, log_index, timestamp
+        )
+        SELECT $1, $2, $3, $4,
+            $5::NUMERIC, $6::NUMERIC, $7::NUMERIC, $8::NUMERIC,
+            pt.token0, pt.token1, $9, $10, $11, to_timestamp($12)
+        FROM (SELECT 1) AS one
+        LEFT JOIN pair_tokens pt ON pt.pair_address = $2 AND pt.chainid = $1
+        ON CONFLICT (chainid, tx_hash, log_index) DO NOTHING
+        "#,
+    )
+    .bind(chainid)
+    .bind(log.address().to_string().to_lowercase())
+    .bind(swap.sender.to_string().to_lowercase())
+    .bind(swap.to.to_string().to_lowercase())
+    .bind(swap.amount0In.to_string())
+    .bind(swap.amount1In.to_string())
+    .bind(swap.amount0Out.to_string())
+    .bind(swap.amount1Out.to_string())
+    .bind(block_number as i64)
+    .bind(tx_hash.to_string())
+    .bind(log_index as i64)
+    .bind(timestamp.map(|ts| ts as f64))
+    .execute(pool)
+    .await?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+    use alloy::primitives::{LogData, U256, address};
+
+    #[test]
+    fn test_swap_signature_matches_uniswapv2() {
+        assert_eq!(UniswapV2Swap::SIGNATURE, "Swap(address,uint256,uint256,uint256,uint256,address)");
+    }
+
+    #[test]
+    fn test_decode_swap_log() {
+        let pair = address!("0xb4e16d0168e52d35cacd2c6185b44281ec28c9dc");
+        let swap = UniswapV2Swap {
+            sender: address!("0x7a250d5630b4cf539739df2c5dacb4c659f2488d"),
+            amount0In: U256::ZERO,
+            amount1In: U256::from(10u64).pow(U256::from(18u64)),
+            amount0Out: U256::from(2_500_000_000u64),
+            amount1Out: U256::ZERO,
+            to: address!("0x000000000000000000000000000000000000dead"),
+        };
+        let log = Log {
+            inner: alloy::primitives::Log {
+                address: pair,
+                data: LogData::from(&swap),
+            },
+            ..Default::default()
+        };
+
+        let decoded = log.log_decode::<UniswapV2Swap>().unwrap().inner.data;
+        assert_eq!(decoded.sender, swap.sender);
+        assert_eq!(decoded.to, swap.to);
+        assert_eq!(decoded.amount1In.to_string(), "1000000000000000000");
+        assert_eq!(decoded.amount0Out.to_string(), "2500000000");
+    }
+}
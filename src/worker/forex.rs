@@ -1,9 +1,10 @@
 use crate::config::Config;
-use anyhow::Result;
-use serde_json::Value;
+use crate::worker::SyncStats;
+use anyhow::{Context, Result, anyhow};
+use serde_json::{Value, json};
 use std::time::Duration;
 use tokio::time::sleep;
-use tracing::{info, warn};
+use tracing::{info, instrument, warn};
 
 /// Maximum number of retry attempts for API requests
 const MAX_RETRY: u8 = 3;
@@ -12,10 +13,11 @@ const RETRY_DELAY_MS: u64 = 300;
 
 // ============= HTTP Fetch with Retry Logic =============
 
-/// Fetches forex data from OpenExchangeRates API with exponential backoff retry
+/// Fetches a forex JSON document with exponential backoff retry
 ///
 /// # Arguments
-/// * `config` - Application configuration containing API key and HTTP client
+/// * `config` - Application configuration containing the HTTP client
+/// * `api_url` - Provider URL, including its API key
 ///
 /// # Returns
 /// * `Ok(Value)` - JSON response from the API on success
@@ -25,17 +27,11 @@ const RETRY_DELAY_MS: u64 = 300;
 /// - Attempts up to MAX_RETRY times (default: 3)
 /// - Uses exponential backoff: 300ms, 600ms between retries
 /// - Does not wait after the final failed attempt
-async fn get_forex_with_retry(config: &Config) -> Result<Value> {
-    // Construct API URL with authentication key
-    let api_url = format!(
-        "https://openexchangerates.org/api/latest.json?app_id={}",
-        config.openexchangerates_key
-    );
-
+async fn get_forex_with_retry(config: &Config, api_url: &str) -> Result<Value> {
     // Retry loop with exponential backoff
     for attempt in 1..=MAX_RETRY {
         // Send HTTP GET request
-        match config.http_client.get(&api_url).send().await {
+        match config.http_get(api_url).send().await {
             Ok(resp) => {
                 // Check if HTTP status is successful (2xx)
                 match resp.error_for_status() {
@@ -86,20 +82,121 @@ async fn get_forex_with_retry(config: &Config) -> Result<Value> {
     ))
 }
 
+// ============= Providers =============
+
+/// Source of USD-based exchange rates
+trait ForexProvider {
+    /// Provider name used in logs and stored as `source`
+    fn name(&self) -> &'static str;
+
+    /// Fetches the latest rates, normalized by [`normalize_rates`]
+    async fn fetch_rates(&self, config: &Config) -> Result<Value>;
+}
+
+/// OpenExchangeRates `latest.json` (primary provider)
+struct OpenExchangeRatesProvider<'a> {
+    app_id: &'a str,
+}
+
+impl ForexProvider for OpenExchangeRatesProvider<'_> {
+    fn name(&self) -> &'static str {
+        "openexchangerates"
+    }
+
+    async fn fetch_rates(&self, config: &Config) -> Result<Value> {
+        let url = format!("https://openexchangerates.org/api/latest.json?app_id={}", self.app_id);
+        let raw = get_forex_with_retry(config, &url).await?;
+        normalize_rates(self.name(), &raw, "base", "rates", "timestamp")
+    }
+}
+
+/// ExchangeRate-API v6 `latest/USD` (fallback provider)
+struct ExchangeRateApiProvider<'a> {
+    key: &'a str,
+}
+
+impl ForexProvider for ExchangeRateApiProvider<'_> {
+    fn name(&self) -> &'static str {
+        "exchangerate-api"
+    }
+
+    async fn fetch_rates(&self, config: &Config) -> Result<Value> {
+        let url = format!("https://v6.exchangerate-api.com/v6/{}/latest/USD", self.key);
+        let raw = get_forex_with_retry(config, &url).await?;
+        // Errors (e.g. an invalid key) can come back as HTTP 200
+        if raw.get("result").and_then(Value::as_str) != Some("success") {
+            return Err(anyhow!(
+                "ExchangeRate-API error: {}",
+                raw.get("error-type").and_then(Value::as_str).unwrap_or("unknown")
+            ));
+        }
+        normalize_rates(self.name(), &raw, "base_code", "conversion_rates", "time_last_update_unix")
+    }
+}
+
+/// Converts a provider response into the stored `{base, rates, timestamp, source}` document
+///
+/// # Arguments
+/// * `source` - Provider name
+/// * `raw` - Provider response
+/// * `base_key`, `rates_key`, `timestamp_key` - Where the provider puts the base
+///   currency, the `{CURRENCY: rate}` map and the Unix update time
+///
+/// # Errors
+/// Fails if the rates map is missing or empty
+fn normalize_rates(source: &str, raw: &Value, base_key: &str, rates_key: &str, timestamp_key: &str) -> Result<Value> {
+    let rates = raw
+        .get(rates_key)
+        .and_then(Value::as_object)
+        .filter(|rates| !rates.is_empty())
+        .with_context(|| format!("{} response has no {}", source, rates_key))?;
+    Ok(json!({
+        "base": raw.get(base_key).and_then(Value::as_str).unwrap_or("USD"),
+        "rates": rates,
+        "timestamp": raw.get(timestamp_key),
+        "source": source,
+    }))
+}
+
+/// Fetches rates from OpenExchangeRates, falling back to ExchangeRate-API
+/// when it fails and `forex_fallback_key` is configured
+async fn fetch_forex_rates(config: &Config) -> Result<Value> {
+    let primary = OpenExchangeRatesProvider { app_id: &config.openexchangerates_key };
+    let err = match primary.fetch_rates(config).await {
+        Ok(rates) => return Ok(rates),
+        Err(e) => e,
+    };
+
+    let Some(key) = config.forex_fallback_key.as_deref() else {
+        return Err(err);
+    };
+    let fallback = ExchangeRateApiProvider { key };
+    warn!("⚠️ {} failed ({:#}), falling back to {}", primary.name(), err, fallback.name());
+    fallback
+        .fetch_rates(config)
+        .await
+        .with_context(|| format!("{} failed too (primary: {:#})", fallback.name(), err))
+}
+
 // ============= Forex Rate Update Function =============
 
 /// Updates forex exchange rates in the database
 ///
 /// This function performs an atomic replacement of all forex data:
-/// 1. Fetches latest rates from OpenExchangeRates API
+/// 1. Fetches latest rates from OpenExchangeRates API (ExchangeRate-API as
+///    fallback), normalized to `{base, rates, timestamp, source}`
 /// 2. Truncates the existing table
 /// 3. Inserts new data with current timestamp
-
-pub async fn update_forex(config: &Config) -> Result<()> {
+///
+/// # Returns
+/// * `Ok(SyncStats)` - `inserted` is the number of currency rates stored
+/// * `Err(anyhow::Error)` - Fetch or database update failed
+#[instrument(skip_all)]
+pub async fn update_forex(config: &Config) -> Result<SyncStats> {
     let pool = &config.postgres_db.pool;
 
     // Step 1: Fetch latest forex data from API (with retry logic)
-    let forex_json = get_forex_with_retry(config).await?;
+    let forex_json = fetch_forex_rates(config).await?;
 
     // Step 2: Atomic database update using transaction
     let mut tx = pool.begin().await?;
@@ -118,7 +215,62 @@ pub async fn update_forex(config: &Config) -> Result<()> {
     // Commit transaction - both operations succeed together
     tx.commit().await?;
 
-    info!("✅ Forex data updated successfully.");
-    Ok(())
+    let rate_count = forex_json
+        .get("rates")
+        .and_then(|r| r.as_object())
+        .map(|r| r.len())
+        .unwrap_or(0);
+
+    info!("✅ Forex data updated successfully ({} rates).", rate_count);
+    Ok(SyncStats {
+        inserted: rate_count,
+        pages: 1,
+        ..Default::default()
+    })
 }
 
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_normalize_openexchangerates() {
+        let raw = json!({
+            "disclaimer": "Usage subject to terms",
+            "timestamp": 1_700_000_000,
+            "base": "USD",
+            "rates": {"EUR": 0.92, "JPY": 149.5}
+        });
+        assert_eq!(
+            normalize_rates("openexchangerates", &raw, "base", "rates", "timestamp").unwrap(),
+            json!({
+                "base": "USD",
+                "rates": {"EUR": 0.92, "JPY": 149.5},
+                "timestamp": 1_700_000_000,
+                "source": "openexchangerates"
+            })
+        );
+    }
+
+    #[test]
+    fn test_normalize_exchangerate_api() {
+        let raw = json!({
+            "result": "success",
+            "time_last_update_unix": 1_700_000_000,
+            "base_code": "USD",
+            "conversion_rates": {"USD": 1, "EUR": 0.92}
+        });
+        let normalized =
+            normalize_rates("exchangerate-api", &raw, "base_code", "conversion_rates", "time_last_update_unix").unwrap();
+        assert_eq!(normalized["base"], "USD");
+        assert_eq!(normalized["rates"]["EUR"], 0.92);
+        assert_eq!(normalized["timestamp"], 1_700_000_000);
+    }
+
+    #[test]
+    fn test_normalize_rejects_missing_rates() {
+        assert!(normalize_rates("openexchangerates", &json!({"base": "USD"}), "base", "rates", "timestamp").is_err());
+        assert!(normalize_rates("openexchangerates", &json!({"rates": {}}), "base", "rates", "timestamp").is_err());
+    }
+}
@@ -1,11 +1,16 @@
 use crate::config::Config;
+use crate::utils::{
+    DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, FetchResult, RetryConfig, get_json_with_retry, new_run_id, retry_db,
+};
+use crate::worker::SyncStats;
 use anyhow::{Context, Result};
-use reqwest::Client;
-use serde::Deserialize;
+use futures::stream::{self, StreamExt, TryStreamExt};
+use serde::{Deserialize, Serialize};
 use sqlx::{Postgres, QueryBuilder, Transaction, Executor};
+use std::pin::pin;
 use std::time::Duration;
 use tokio::time::sleep;
-use tracing::{info, warn};
+use tracing::{info, instrument};
 
 /// Maximum number of retry attempts for API requests
 const MAX_RETRIES: u32 = 3;
@@ -13,6 +18,8 @@ const MAX_RETRIES: u32 = 3;
 const TOKENS_PER_PAGE: u32 = 250;
 /// Rate limit delay between API requests (milliseconds)
 const RATE_LIMIT_DELAY_MS: u64 = 300;
+/// Number of pages fetched concurrently
+const PAGE_FETCH_CONCURRENCY: usize = 4;
 
 /// Market data structure from CoinGecko API
 ///
@@ -54,15 +61,56 @@ pub struct MarketData {
     pub atl_date: Option<String>,
     /// Timestamp of last update
     pub last_updated: Option<String>,
+    /// 7-day hourly price series (only present when requested with `sparkline=true`)
+    #[serde(default)]
+    pub sparkline_in_7d: Option<SparklineData>,
+}
+
+/// CoinGecko `sparkline_in_7d` object
+#[derive(Deserialize, Serialize, Debug, Clone)]
+pub struct SparklineData {
+    /// Hourly USD prices, oldest first (~168 points)
+    pub price: Vec<f64>,
+}
+
+/// Builds the `/coins/markets` URL for one page
+///
+/// # Arguments
+/// * `base_url` - CoinGecko API base URL (no trailing slash)
+/// * `page` - Page number (1-indexed)
+/// * `sparkline` - Whether to request the 7-day sparkline (much larger response)
+fn markets_url(base_url: &str, page: u32, sparkline: bool) -> String {
+    format!(
+        "{}/api/v3/coins/markets?vs_currency=usd&per_page={}&page={}&sparkline={}",
+        base_url, TOKENS_PER_PAGE, page, sparkline
+    )
+}
+
+/// Builds the `/coins/markets` URL for specific token IDs
+///
+/// # Arguments
+/// * `base_url` - CoinGecko API base URL (no trailing slash)
+/// * `tokenids` - CoinGecko token IDs (at most [`TOKENS_PER_PAGE`])
+/// * `sparkline` - Whether to request the 7-day sparkline
+fn markets_ids_url(base_url: &str, tokenids: &[String], sparkline: bool) -> String {
+    format!(
+        "{}/api/v3/coins/markets?vs_currency=usd&ids={}&per_page={}&sparkline={}",
+        base_url,
+        tokenids.join(","),
+        TOKENS_PER_PAGE,
+        sparkline
+    )
 }
 
 /// Fetches one page of market data from CoinGecko API
 ///
-/// Implements retry logic with exponential backoff for failed requests.
+/// Retries through [`get_json_with_retry`] with the shared CoinGecko rate
+/// limiter. The circuit breaker is not consulted here: [`sync_marketdata`]
+/// checks it once for the whole run, so concurrent pages never compete for
+/// a half-open probe.
 ///
 /// # Arguments
-/// * `client` - HTTP client for making requests
-/// * `api_key` - CoinGecko API key for authentication
+/// * `config` - Application configuration (HTTP client, API key, rate limiter)
 /// * `page` - Page number (1-indexed)
 ///
 /// # Returns
@@ -71,45 +119,30 @@ pub struct MarketData {
 ///
 /// # Rate Limiting
 /// Uses CoinGecko free tier: 250 tokens per page
-async fn fetch_tokens_page(client: &Client, api_key: &str, page: u32) -> Result<Vec<MarketData>> {
-    let url = format!(
-        "https://api.coingecko.com/api/v3/coins/markets?vs_currency=usd&per_page={}&page={}",
-        TOKENS_PER_PAGE, page
-    );
-
-    let mut retries = MAX_RETRIES;
-    loop {
-        let resp = client
-            .get(&url)
-            .header("x-cg-demo-api-key", api_key)
-            .header("Accept", "application/json")
-            .send()
-            .await
-            .context("HTTP request failed")?;
-
-        // Check HTTP status
-        if !resp.status().is_success() {
-            retries -= 1;
-            if retries == 0 {
-                anyhow::bail!("Failed to fetch page {}: HTTP {}", page, resp.status());
-            }
-            warn!(
-                "HTTP {} on page {} (attempt {}/{}), retrying...",
-                resp.status(),
-                page,
-                MAX_RETRIES - retries,
-                MAX_RETRIES
-            );
-            sleep(Duration::from_secs(1)).await;
-            continue;
-        }
-
-        // Parse JSON response
-        let tokens: Vec<MarketData> = resp
-            .json()
-            .await
-            .context("Failed to parse JSON from CoinGecko")?;
-        return Ok(tokens);
+#[instrument(skip(config))]
+async fn fetch_tokens_page(config: &Config, page: u32) -> Result<Vec<MarketData>> {
+    let url = markets_url(&config.coingecko_base_url, page, config.fetch_sparkline);
+    match get_json_with_retry::<Vec<MarketData>>(
+        config,
+        &url,
+        |r| {
+            r.header("x-cg-demo-api-key", &config.coingecko_key)
+                .header("Accept", "application/json")
+        },
+        RetryConfig {
+            max_retry: MAX_RETRIES as usize,
+            max_consecutive_fail: MAX_RETRIES as usize,
+            ..config.retry_config()
+        },
+        None,
+        Some(&config.coingecko_rate_limiter),
+    )
+    .await
+    {
+        FetchResult::Success(tokens) => Ok(tokens),
+        FetchResult::Empty => Ok(Vec::new()),
+        FetchResult::NotFound => anyhow::bail!("Failed to fetch page {}: endpoint not found", page),
+        FetchResult::Failed(e) => anyhow::bail!("Failed to fetch page {}: {}", page, e),
     }
 }
 
@@ -144,7 +177,7 @@ async fn insert_bulk_tokens(
             token_id, symbol, name, image, market_cap, market_cap_rank,
             fully_diluted_valuation, price_change_24h, price_change_percentage_24h,
             circulating_supply, total_supply, max_supply, ath, ath_date,
-            atl, atl_date, last_updated
+            atl, atl_date, last_updated, sparkline_7d
         ) ",
     );
 
@@ -154,19 +187,25 @@ async fn insert_bulk_tokens(
             .push_bind(&token.symbol)
             .push_bind(&token.name)
             .push_bind(&token.image)
-            .push_bind(&token.market_cap)
-            .push_bind(&token.market_cap_rank)
-            .push_bind(&token.fully_diluted_valuation)
-            .push_bind(&token.price_change_24h)
-            .push_bind(&token.price_change_percentage_24h)
-            .push_bind(&token.circulating_supply)
-            .push_bind(&token.total_supply)
-            .push_bind(&token.max_supply)
-            .push_bind(&token.ath)
+            .push_bind(token.market_cap)
+            .push_bind(token.market_cap_rank)
+            .push_bind(token.fully_diluted_valuation)
+            .push_bind(token.price_change_24h)
+            .push_bind(token.price_change_percentage_24h)
+            .push_bind(token.circulating_supply)
+            .push_bind(token.total_supply)
+            .push_bind(token.max_supply)
+            .push_bind(token.ath)
             .push_bind(&token.ath_date)
-            .push_bind(&token.atl)
+            .push_bind(token.atl)
             .push_bind(&token.atl_date)
-            .push_bind(&token.last_updated);
+            .push_bind(&token.last_updated)
+            .push_bind(
+                token
+                    .sparkline_in_7d
+                    .as_ref()
+                    .map(|s| sqlx::types::Json(&s.price)),
+            );
     });
 
     // Execute the bulk insert
@@ -178,33 +217,145 @@ async fn insert_bulk_tokens(
     Ok(())
 }
 
-/// Synchronizes cryptocurrency market data from CoinGecko
+/// Overwrites the stored market data of one token
 ///
-/// This is the main entry point for market data synchronization.
-/// It performs a full data refresh by:
-/// 1. Truncating the existing marketdata table
-/// 2. Fetching all pages from CoinGecko API
-/// 3. Bulk inserting data in a single transaction
+/// # Returns
+/// * `Ok(true)` - Row updated
+/// * `Ok(false)` - Token has no row in `marketdata`
+async fn update_token(tx: &mut Transaction<'_, Postgres>, token: &MarketData) -> Result<bool> {
+    let result = sqlx::query(
+        r#"
+        UPDATE marketdata SET
+            symbol = $2, name = $3, image = $4, market_cap = $5, market_cap_rank = $6,
+            fully_diluted_valuation = $7, price_change_24h = $8, price_change_percentage_24h = $9,
+            circulating_supply = $10, total_supply = $11, max_supply = $12, ath = $13, ath_date = $14,
+            atl = $15, atl_date = $16, last_updated = $17,
+            sparkline_7d = COALESCE($18, sparkline_7d)
+        WHERE token_id = $1
+        "#,
+    )
+    .bind(&token.id)
+    .bind(&token.symbol)
+    .bind(&token.name)
+    .bind(&token.image)
+    .bind(token.market_cap)
+    .bind(token.market_cap_rank)
+    .bind(token.fully_diluted_valuation)
+    .bind(token.price_change_24h)
+    .bind(token.price_change_percentage_24h)
+    .bind(token.circulating_supply)
+    .bind(token.total_supply)
+    .bind(token.max_supply)
+    .bind(token.ath)
+    .bind(&token.ath_date)
+    .bind(token.atl)
+    .bind(&token.atl_date)
+    .bind(&token.last_updated)
+    .bind(token.sparkline_in_7d.as_ref().map(|s| sqlx::types::Json(&s.price)))
+    .execute(&mut **tx)
+    .await
+    .with_context(|| format!("Failed to update marketdata for {}", token.id))?;
+
+    Ok(result.rows_affected() > 0)
+}
+
+/// Outcome of an on-demand market data refresh
+#[derive(Debug, Default, Serialize)]
+pub struct RefreshOutcome {
+    /// Token IDs whose `marketdata` rows were updated
+    pub updated: Vec<String>,
+    /// Token IDs unknown to CoinGecko or without a `marketdata` row
+    pub not_found: Vec<String>,
+}
+
+/// Refreshes the market data of specific tokens without a full sync
+///
+/// Requests `/coins/markets?ids=...` in chunks of [`TOKENS_PER_PAGE`] and
+/// updates the matching `marketdata` rows. Rows are only updated, never
+/// inserted: tokens missing from `marketdata` are reported in `not_found`.
 ///
 /// # Arguments
 /// * `config` - Application configuration with database pool and API keys
+/// * `tokenids` - CoinGecko token IDs
 ///
 /// # Returns
-/// * `Ok(())` - Sync completed successfully
-/// * `Err(anyhow::Error)` - Sync failed (transaction rolled back)
+/// * `Ok(RefreshOutcome)` - Updated and not-found token IDs
+/// * `Err(anyhow::Error)` - CoinGecko request or database update failed
+#[instrument(name = "sync_run", skip_all, fields(pipeline = "refresh_market_data", run_id = %new_run_id()))]
+pub async fn refresh_tokens_market_data(config: &Config, tokenids: &[String]) -> Result<RefreshOutcome> {
+    let mut outcome = RefreshOutcome::default();
+
+    for (i, chunk) in tokenids.chunks(TOKENS_PER_PAGE as usize).enumerate() {
+        if i > 0 {
+            sleep(Duration::from_millis(RATE_LIMIT_DELAY_MS)).await;
+        }
+
+        let url = markets_ids_url(&config.coingecko_base_url, chunk, config.fetch_sparkline);
+        let tokens = match get_json_with_retry::<Vec<MarketData>>(
+            config,
+            &url,
+            |r| {
+                r.header("x-cg-demo-api-key", &config.coingecko_key)
+                    .header("Accept", "application/json")
+            },
+            RetryConfig {
+                max_retry: MAX_RETRIES as usize,
+                max_consecutive_fail: MAX_RETRIES as usize,
+                ..config.retry_config()
+            },
+            Some(&config.coingecko_breaker),
+            Some(&config.coingecko_rate_limiter),
+        )
+        .await
+        {
+            FetchResult::Success(tokens) => tokens,
+            FetchResult::Empty => Vec::new(),
+            FetchResult::NotFound => anyhow::bail!("Failed to fetch market data: endpoint not found"),
+            FetchResult::Failed(e) => anyhow::bail!("Failed to fetch market data: {}", e),
+        };
+
+        let mut tx = config.postgres_db.pool.begin().await?;
+        for token in &tokens {
+            if update_token(&mut tx, token).await? {
+                outcome.updated.push(token.id.clone());
+            }
+        }
+        tx.commit().await?;
+
+        outcome.not_found.extend(
+            chunk
+                .iter()
+                .filter(|id| !outcome.updated.contains(id))
+                .cloned(),
+        );
+    }
+
+    info!(
+        "✅ Refreshed market data: {} updated, {} not found",
+        outcome.updated.len(),
+        outcome.not_found.len()
+    );
+    Ok(outcome)
+}
+
+/// Drops tokens whose market cap is below `min_market_cap_usd`
 ///
-/// # Performance Characteristics
-/// - Uses pagination (250 tokens per page)
-/// - Respects CoinGecko rate limits (300ms between requests)
-/// - Single transaction ensures atomicity
-/// - Typical runtime: ~1-2 minutes for ~10,000 tokens
+/// Tokens without a market cap count as 0. Nothing is dropped when no
+/// minimum is configured.
 ///
-/// # Database Schema
-/// Requires the `marketdata` table to exist (created via migrations)
-pub async fn sync_marketdata(config: &Config) -> Result<()> {
-    info!("🚀 Market data synchronization started");
+/// # Returns
+/// Number of tokens dropped
+fn filter_dust_tokens(tokens: &mut Vec<MarketData>, min_market_cap_usd: Option<f64>) -> usize {
+    let Some(min) = min_market_cap_usd else {
+        return 0;
+    };
+    let before = tokens.len();
+    tokens.retain(|t| t.market_cap.unwrap_or(0.0) >= min);
+    before - tokens.len()
+}
 
-    // Start database transaction
+/// Replaces the contents of `marketdata` with the given pages in one transaction
+async fn replace_marketdata(config: &Config, pages: &[Vec<MarketData>]) -> Result<()> {
     let mut tx = config
         .postgres_db
         .pool
@@ -217,37 +368,59 @@ pub async fn sync_marketdata(config: &Config) -> Result<()> {
         .await
         .context("Failed to truncate marketdata table")?;
 
-    // Fetch and insert data page by page
-    let mut page = 1;
-    let mut total_tokens = 0;
-    
-    loop {
-        // Fetch one page of data
-        let tokens = fetch_tokens_page(&config.http_client, &config.coingecko_key, page)
-            .await
-            .with_context(|| format!("Failed to fetch page {}", page))?;
-
-        // Empty response means we've reached the end
-        if tokens.is_empty() {
-            info!("Reached end of data at page {}", page);
-            break;
-        }
+    for tokens in pages {
+        insert_bulk_tokens(&mut tx, tokens).await?;
+    }
 
-        let token_count = tokens.len();
-        total_tokens += token_count;
+    // Commit all changes atomically
+    tx.commit().await.context("Failed to commit transaction")?;
+    Ok(())
+}
 
-        // Bulk insert this page's data
-        insert_bulk_tokens(&mut tx, &tokens).await?;
-        info!("✓ Page {}: inserted {} tokens (total: {})", page, token_count, total_tokens);
+/// Synchronizes cryptocurrency market data from CoinGecko
+///
+/// This is the main entry point for market data synchronization.
+/// It performs a full data refresh by:
+/// 1. Fetching all pages (up to `max_market_data_pages`) from CoinGecko API,
+///    dropping tokens below `min_market_cap_usd`
+/// 2. Truncating the existing marketdata table and bulk inserting the
+///    fetched data in a single transaction, retried from scratch if the
+///    database connection drops (see [`retry_db`])
+///
+/// # Arguments
+/// * `config` - Application configuration with database pool and API keys
+///
+/// # Returns
+/// * `Ok(SyncStats)` - Sync completed successfully (tokens inserted, pages fetched)
+/// * `Err(anyhow::Error)` - Sync failed (transaction rolled back)
+///
+/// # Performance Characteristics
+/// - Uses pagination (250 tokens per page), up to 4 pages in flight
+/// - Respects CoinGecko rate limits (300ms between request starts)
+/// - Single transaction ensures atomicity; it stays open only for the writes
+/// - Typical runtime: ~1-2 minutes for ~10,000 tokens
+///
+/// # Database Schema
+/// Requires the `marketdata` table to exist (created via migrations)
+#[instrument(skip_all)]
+pub async fn sync_marketdata(config: &Config) -> Result<SyncStats> {
+    info!("🚀 Market data synchronization started");
 
-        page += 1;
-        
-        // Rate limiting: wait before next request
-        sleep(Duration::from_millis(RATE_LIMIT_DELAY_MS)).await;
+    // One breaker check per run: after a cooldown the whole page fetch is
+    // the half-open probe
+    let breaker = &config.coingecko_breaker;
+    if !breaker.allow_request() {
+        anyhow::bail!("CoinGecko circuit breaker is open, skipping market data sync");
+    }
+    let result = fetch_all_pages(config).await;
+    match &result {
+        Ok(_) => breaker.record_success(),
+        Err(_) => breaker.record_failure(),
     }
+    let (fetched, pages, total_tokens) = result?;
 
-    // Commit all changes atomically
-    tx.commit().await.context("Failed to commit transaction")?;
+    // Replace all rows atomically; a dropped connection rolls back and retries
+    retry_db("marketdata sync", DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, || replace_marketdata(config, &fetched)).await?;
 
     // Verify final count
     let row_count: (i64,) = sqlx::query_as("SELECT COUNT(*) FROM marketdata")
@@ -257,9 +430,74 @@ pub async fn sync_marketdata(config: &Config) -> Result<()> {
 
     info!(
         "✅ Market data sync completed: {} tokens across {} pages",
-        row_count.0, page - 1
+        row_count.0, pages
     );
-    Ok(())
+    Ok(SyncStats {
+        inserted: total_tokens,
+        pages: pages as usize,
+        ..Default::default()
+    })
+}
+
+/// Fetches every `/coins/markets` page of a [`sync_marketdata`] run
+///
+/// Drops tokens below `min_market_cap_usd` as pages arrive.
+///
+/// # Returns
+/// * `Ok((pages, page_count, token_count))` - Non-empty pages in page order
+/// * `Err(anyhow::Error)` - A page failed after all retries
+async fn fetch_all_pages(config: &Config) -> Result<(Vec<Vec<MarketData>>, u32, usize)> {
+    // Fetch pages concurrently; `buffered` yields them in page order.
+    // Request starts stay spaced by RATE_LIMIT_DELAY_MS, so only the
+    // waiting on responses overlaps.
+    let last_page = config.max_market_data_pages.unwrap_or(u32::MAX);
+    let mut pages = pin!(stream::iter(1u32..=last_page)
+        .then(|page| async move {
+            sleep(Duration::from_millis(RATE_LIMIT_DELAY_MS)).await;
+            page
+        })
+        .map(|page| async move {
+            fetch_tokens_page(config, page)
+                .await
+                .with_context(|| format!("Failed to fetch page {}", page))
+                .map(|tokens| (page, tokens))
+        })
+        .buffered(PAGE_FETCH_CONCURRENCY));
+
+    let mut page = 1;
+    let mut total_tokens = 0;
+    let mut total_filtered = 0;
+    let mut fetched = Vec::new();
+
+    while let Some((fetched_page, mut tokens)) = pages.try_next().await? {
+        // Empty response means we've reached the end; pages still in
+        // flight past the end are dropped
+        if tokens.is_empty() {
+            info!("Reached end of data at page {}", fetched_page);
+            break;
+        }
+
+        let filtered = filter_dust_tokens(&mut tokens, config.min_market_cap_usd);
+        total_filtered += filtered;
+        let token_count = tokens.len();
+        total_tokens += token_count;
+
+        info!(
+            "✓ Page {}: fetched {} tokens, {} below min market cap (total: {})",
+            fetched_page, token_count, filtered, total_tokens
+        );
+        fetched.push(tokens);
+
+        page = fetched_page + 1;
+    }
+
+    if page > last_page {
+        info!("Stopped at MAX_MARKET_DATA_PAGES ({})", last_page);
+    }
+    if total_filtered > 0 {
+        info!("🧹 Skipped {} tokens below MIN_MARKET_CAP_USD", total_filtered);
+    }
+    Ok((fetched, page - 1, total_tokens))
 }
 
 // ============= Unit Tests =============
@@ -273,6 +511,7 @@ mod tests {
         assert_eq!(MAX_RETRIES, 3);
         assert_eq!(TOKENS_PER_PAGE, 250);
         assert_eq!(RATE_LIMIT_DELAY_MS, 300);
+        assert_eq!(PAGE_FETCH_CONCURRENCY, 4);
     }
 
     #[test]
@@ -305,6 +544,41 @@ mod tests {
         assert_eq!(data.symbol, "btc");
         assert_eq!(data.name, "Bitcoin");
         assert_eq!(data.market_cap_rank, Some(1));
+        assert!(data.sparkline_in_7d.is_none());
+    }
+
+    #[test]
+    fn test_market_data_with_sparkline() {
+        let json = r#"{
+            "id": "ethereum",
+            "symbol": "eth",
+            "name": "Ethereum",
+            "sparkline_in_7d": {"price": [3000.5, 3010.25, 2995.0]}
+        }"#;
+
+        let data: MarketData = serde_json::from_str(json).unwrap();
+        let sparkline = data.sparkline_in_7d.unwrap();
+        assert_eq!(sparkline.price, vec![3000.5, 3010.25, 2995.0]);
+    }
+
+    #[test]
+    fn test_markets_url() {
+        let url = markets_url("https://api.coingecko.com", 2, false);
+        assert_eq!(
+            url,
+            "https://api.coingecko.com/api/v3/coins/markets?vs_currency=usd&per_page=250&page=2&sparkline=false"
+        );
+        assert!(markets_url("http://localhost:8080", 1, true).starts_with("http://localhost:8080/api/v3/"));
+        assert!(markets_url("http://localhost:8080", 1, true).ends_with("&sparkline=true"));
+    }
+
+    #[test]
+    fn test_markets_ids_url() {
+        let ids = vec!["bitcoin".to_string(), "ethereum".to_string()];
+        assert_eq!(
+            markets_ids_url("https://api.coingecko.com", &ids, false),
+            "https://api.coingecko.com/api/v3/coins/markets?vs_currency=usd&ids=bitcoin,ethereum&per_page=250&sparkline=false"
+        );
     }
 
     #[test]
@@ -324,4 +598,23 @@ mod tests {
         assert!(data.market_cap.is_none());
         assert!(data.ath.is_none());
     }
+
+    #[test]
+    fn test_filter_dust_tokens() {
+        let token = |id: &str, market_cap: Option<f64>| {
+            let mut data: MarketData =
+                serde_json::from_value(serde_json::json!({"id": id, "symbol": id, "name": id})).unwrap();
+            data.market_cap = market_cap;
+            data
+        };
+        let page = || vec![token("big", Some(5e6)), token("dust", Some(10.0)), token("unranked", None)];
+
+        let mut tokens = page();
+        assert_eq!(filter_dust_tokens(&mut tokens, None), 0);
+        assert_eq!(tokens.len(), 3);
+
+        let mut tokens = page();
+        assert_eq!(filter_dust_tokens(&mut tokens, Some(1e6)), 2);
+        assert_eq!(tokens.iter().map(|t| t.id.as_str()).collect::<Vec<_>>(), ["big"]);
+    }
 }
@@ -1,117 +1,525 @@
 use crate::config::Config;
-use crate::utils::{FetchResult, get_json_with_retry};
+use crate::utils::{
+    DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, FetchResult, RetryConfig, get_json_with_retry, get_json_with_retry_with, retry_db,
+};
+use crate::worker::SyncStats;
+use crate::worker::risk::{RiskInputs, compute_risk};
 use anyhow::{Context, Result, anyhow};
+use chrono::NaiveDate;
+use futures::stream::{self, StreamExt};
+use futures::{FutureExt, future};
 use serde::Deserialize;
 use serde_json::Value;
-use sqlx::PgPool;
-use std::collections::HashMap;
-use std::time::Duration;
+use sqlx::{PgPool, Postgres, QueryBuilder};
+use std::collections::{HashMap, HashSet};
+use std::pin::pin;
+use std::sync::atomic::{AtomicUsize, Ordering};
+use std::time::{Duration, Instant};
 use tokio::time::sleep;
-use tracing::{info, warn};
+use tracing::{info, instrument, warn};
+
+// ================== CoinGecko Response Structures ==================
+
+/// One entry of CoinGecko `/coins/list?include_platform=true`
+///
+/// Fields are optional so a single malformed entry is skipped instead of
+/// failing the whole list.
+#[derive(Debug, Deserialize)]
+struct CoinListEntry {
+    id: Option<String>,
+    symbol: Option<String>,
+    name: Option<String>,
+    /// Platform slug -> contract address (null/empty for native coins)
+    platforms: Option<HashMap<String, Option<String>>>,
+}
+
+/// `/coins/list` entries with a contract on at least one relevant platform
+struct RelevantCoinList {
+    /// Kept entries, with `platforms` pruned to relevant non-empty addresses
+    entries: Vec<CoinListEntry>,
+    /// Number of entries in the response
+    total: usize,
+}
+
+/// Deserializes `/coins/list` into a [`RelevantCoinList`]
+///
+/// Entries are parsed one at a time and dropped right away unless they carry
+/// an id, symbol, name and an address on a platform accepted by
+/// `is_relevant`, so the tens of thousands of native or unindexed coins are
+/// never collected.
+#[derive(Clone, Copy)]
+struct RelevantCoinListSeed<'a> {
+    is_relevant: &'a (dyn Fn(&str) -> bool + Sync),
+}
+
+impl RelevantCoinListSeed<'_> {
+    /// Parses a complete response body
+    fn parse(self, text: &str) -> serde_json::Result<RelevantCoinList> {
+        let mut deserializer = serde_json::Deserializer::from_str(text);
+        let list = serde::de::DeserializeSeed::deserialize(self, &mut deserializer)?;
+        deserializer.end()?;
+        Ok(list)
+    }
+}
+
+impl<'de> serde::de::DeserializeSeed<'de> for RelevantCoinListSeed<'_> {
+    type Value = RelevantCoinList;
+
+    fn deserialize<D: serde::Deserializer<'de>>(self, deserializer: D) -> Result<Self::Value, D::Error> {
+        deserializer.deserialize_seq(self)
+    }
+}
+
+impl<'de> serde::de::Visitor<'de> for RelevantCoinListSeed<'_> {
+    type Value = RelevantCoinList;
+
+    fn expecting(&self, f: &mut std::fmt::Formatter) -> std::fmt::Result {
+        f.write_str("a list of coins")
+    }
+
+    fn visit_seq<A: serde::de::SeqAccess<'de>>(self, mut seq: A) -> Result<Self::Value, A::Error> {
+        let mut list = RelevantCoinList { entries: Vec::new(), total: 0 };
+        while let Some(mut entry) = seq.next_element::<CoinListEntry>()? {
+            list.total += 1;
+            if entry.id.is_none() || entry.symbol.is_none() || entry.name.is_none() {
+                continue;
+            }
+            let Some(platforms) = entry.platforms.as_mut() else {
+                continue;
+            };
+            platforms.retain(|platform, address| {
+                address.as_deref().is_some_and(|a| !a.is_empty()) && (self.is_relevant)(platform)
+            });
+            if !platforms.is_empty() {
+                list.entries.push(entry);
+            }
+        }
+        Ok(list)
+    }
+}
+
+/// CoinGecko `/coins/{id}` response (only the fields we store)
+#[derive(Debug, Deserialize)]
+struct CoinDetail {
+    id: String,
+    /// Null for some unmaintained coins, which are then skipped
+    #[serde(default)]
+    symbol: Option<String>,
+    #[serde(default)]
+    name: Option<String>,
+    #[serde(default)]
+    links: Option<CoinLinks>,
+    #[serde(default)]
+    image: Option<CoinImage>,
+    #[serde(default)]
+    description: Option<CoinDescription>,
+    #[serde(default)]
+    additional_notices: Option<Value>,
+    #[serde(default)]
+    developer_data: Option<Value>,
+    /// Community counters (twitter_followers, reddit_subscribers, ...)
+    #[serde(default)]
+    community_data: Option<Value>,
+    /// Exchange listings (only requested when `fetch_tickers` is enabled)
+    #[serde(default)]
+    tickers: Option<Vec<Value>>,
+    /// Platform slug -> contract address on every chain the token is deployed on
+    #[serde(default)]
+    platforms: Option<HashMap<String, Option<String>>>,
+    /// Launch date as `YYYY-MM-DD` (null for many newer tokens)
+    #[serde(default)]
+    genesis_date: Option<String>,
+}
+
+/// Tickers kept per token, highest volume first
+const MAX_STORED_TICKERS: usize = 10;
+
+/// `community_data` counters that `GET /metadata/trending` can sort by
+pub const COMMUNITY_SORT_KEYS: &[&str] = &["twitter_followers", "reddit_subscribers", "telegram_channel_user_count"];
+
+/// `links` object of a coin detail response
+#[derive(Debug, Deserialize)]
+struct CoinLinks {
+    #[serde(default)]
+    homepage: Option<Vec<String>>,
+    #[serde(default)]
+    twitter_screen_name: Option<String>,
+    #[serde(default)]
+    telegram_channel_identifier: Option<String>,
+    #[serde(default)]
+    chat_url: Option<Vec<String>>,
+    #[serde(default)]
+    announcement_url: Option<Vec<String>>,
+    #[serde(default)]
+    repos_url: Option<CoinRepos>,
+}
+
+/// `links.repos_url` object of a coin detail response
+#[derive(Debug, Deserialize)]
+struct CoinRepos {
+    #[serde(default)]
+    github: Option<Vec<String>>,
+}
+
+/// Drops empty strings from an optional CoinGecko URL list
+fn non_empty(urls: Option<&Vec<String>>) -> Vec<&str> {
+    urls.into_iter()
+        .flatten()
+        .map(|u| u.as_str())
+        .filter(|u| !u.is_empty())
+        .collect()
+}
+
+/// `image` object of a coin detail response
+#[derive(Debug, Deserialize)]
+struct CoinImage {
+    #[serde(default)]
+    large: Option<String>,
+}
+
+/// `description` object of a coin detail response (localized texts)
+#[derive(Debug, Deserialize)]
+struct CoinDescription {
+    #[serde(default)]
+    en: Option<String>,
+}
+
+impl CoinDetail {
+    /// First non-empty homepage URL
+    fn homepage(&self) -> Option<&str> {
+        self.links
+            .as_ref()?
+            .homepage
+            .as_ref()?
+            .iter()
+            .map(|h| h.as_str())
+            .find(|h| !h.is_empty())
+    }
+
+    /// Social/contact links as a JSON object (None if the coin lists none)
+    fn social_links(&self) -> Option<Value> {
+        let links = self.links.as_ref()?;
+        let twitter = links.twitter_screen_name.as_deref().filter(|s| !s.is_empty());
+        let telegram = links.telegram_channel_identifier.as_deref().filter(|s| !s.is_empty());
+        let github = non_empty(links.repos_url.as_ref().and_then(|r| r.github.as_ref()));
+        let chat = non_empty(links.chat_url.as_ref());
+        let announcement = non_empty(links.announcement_url.as_ref());
+
+        if twitter.is_none()
+            && telegram.is_none()
+            && github.is_empty()
+            && chat.is_empty()
+            && announcement.is_empty()
+        {
+            return None;
+        }
+
+        Some(serde_json::json!({
+            "twitter": twitter,
+            "telegram": telegram,
+            "github": github,
+            "chat": chat,
+            "announcement": announcement,
+        }))
+    }
+
+    /// Large logo URL
+    fn image(&self) -> Option<&str> {
+        self.image.as_ref()?.large.as_deref()
+    }
+
+    /// English description
+    fn description(&self) -> Option<&str> {
+        self.description.as_ref()?.en.as_deref()
+    }
+
+    /// Symbol and name (None if either is missing or empty)
+    fn symbol_and_name(&self) -> Option<(&str, &str)> {
+        let symbol = self.symbol.as_deref().filter(|s| !s.is_empty())?;
+        let name = self.name.as_deref().filter(|n| !n.is_empty())?;
+        Some((symbol, name))
+    }
+
+    /// Numeric community counters (None if the coin reports none)
+    ///
+    /// Nulls and non-numeric values are dropped. The [`COMMUNITY_SORT_KEYS`]
+    /// counters are kept only as non-negative integers, so the trending
+    /// query's `::bigint` cast never fails.
+    fn community_data(&self) -> Option<Value> {
+        let counters: serde_json::Map<String, Value> = self
+            .community_data
+            .as_ref()?
+            .as_object()?
+            .iter()
+            .filter(|(key, value)| {
+                if COMMUNITY_SORT_KEYS.contains(&key.as_str()) {
+                    value.is_u64()
+                } else {
+                    value.is_number()
+                }
+            })
+            .map(|(key, value)| (key.clone(), value.clone()))
+            .collect();
+        (!counters.is_empty()).then_some(Value::Object(counters))
+    }
+
+    /// Developer stats (None if the coin reports none)
+    ///
+    /// `stars` feeds the generated `github_stars INTEGER` column, so it is
+    /// kept only as a non-negative integer that fits; otherwise it is
+    /// dropped rather than failing the whole insert.
+    fn developer_data(&self) -> Option<Value> {
+        let mut stats = self.developer_data.as_ref()?.as_object()?.clone();
+        let stars_fit = stats
+            .get("stars")
+            .and_then(Value::as_u64)
+            .is_some_and(|stars| i32::try_from(stars).is_ok());
+        if !stars_fit {
+            stats.remove("stars");
+        }
+        (!stats.is_empty()).then_some(Value::Object(stats))
+    }
+
+    /// Genesis date (None if missing or not a `YYYY-MM-DD` date)
+    fn genesis_date(&self) -> Option<NaiveDate> {
+        NaiveDate::parse_from_str(self.genesis_date.as_deref()?.trim(), "%Y-%m-%d").ok()
+    }
+
+    /// Top [`MAX_STORED_TICKERS`] exchange tickers by volume (None if tickers were not requested)
+    ///
+    /// Each entry is reduced to `{exchange, base, target, last, volume}`;
+    /// entries without an exchange name are dropped. A coin without listings
+    /// yields an empty array, so a refresh clears tickers it no longer has.
+    fn tickers(&self) -> Option<Value> {
+        let mut tickers: Vec<(f64, Value)> = self
+            .tickers
+            .as_ref()?
+            .iter()
+            .filter_map(|t| {
+                let exchange = t.pointer("/exchange/name")?.as_str()?;
+                let volume = t.get("volume").and_then(Value::as_f64);
+                let ticker = serde_json::json!({
+                    "exchange": exchange,
+                    "base": t.get("base").and_then(Value::as_str),
+                    "target": t.get("target").and_then(Value::as_str),
+                    "last": t.get("last").and_then(Value::as_f64),
+                    "volume": volume,
+                });
+                Some((volume.unwrap_or(0.0), ticker))
+            })
+            .collect();
+        tickers.sort_by(|a, b| b.0.total_cmp(&a.0));
+        tickers.truncate(MAX_STORED_TICKERS);
+        Some(Value::Array(tickers.into_iter().map(|(_, t)| t).collect()))
+    }
+
+    /// Platforms map with EVM addresses lowercased (None if the coin has no contract)
+    ///
+    /// Native coins come back as `{"": ""}`; such empty entries are dropped.
+    /// Non-EVM addresses (e.g. base58 on Solana) are case-sensitive and kept as is.
+    fn platforms(&self) -> Option<Value> {
+        let platforms: serde_json::Map<String, Value> = self
+            .platforms
+            .as_ref()?
+            .iter()
+            .filter_map(|(platform, address)| {
+                let address = address.as_deref().filter(|a| !a.is_empty())?;
+                let address = if address.starts_with("0x") { address.to_lowercase() } else { address.to_string() };
+                (!platform.is_empty()).then(|| (platform.clone(), Value::from(address)))
+            })
+            .collect();
+        (!platforms.is_empty()).then_some(Value::Object(platforms))
+    }
+}
 
 // ================== TokenMap 同步 ==================
-pub async fn sync_tokenmap(config: &Config) -> Result<()> {
+
+/// Rows per multi-row `tokenmap` INSERT (5 binds each, well under the 65535 limit)
+const TOKENMAP_INSERT_CHUNK: usize = 5_000;
+
+/// Rate limiter permits taken by the bulk `/coins/list` call
+const COIN_LIST_WEIGHT: usize = 5;
+
+/// A `tokenmap` or `nftmap` row collected by [`sync_tokenmap`] / [`sync_nftmap`]
+struct MapRow {
+    /// CoinGecko token or NFT collection ID
+    id: String,
+    symbol: String,
+    name: String,
+    chainid: i64,
+    address: String,
+}
+
+#[instrument(skip_all)]
+pub async fn sync_tokenmap(config: &Config) -> Result<SyncStats> {
     info!("🔄 Syncing tokenmap from Coingecko...");
 
     let pool = &config.postgres_db.pool;
     let mut inserted = 0usize;
-    let mut skipped = 0usize;
 
-    let url = "https://api.coingecko.com/api/v3/coins/list?include_platform=true";
-    let result = get_json_with_retry::<Value>(
+    let chains_map: HashMap<String, i64> =
+        sqlx::query_as::<_, (String, i64)>("SELECT name, chainid FROM chains")
+            .fetch_all(pool)
+            .await
+            .context("Failed to load chains")?
+            .into_iter()
+            .collect();
+
+    // Coins without a contract on an indexed chain are dropped while parsing
+    let is_relevant = |platform: &str| chains_map.contains_key(platform) && config.is_platform_indexed(platform);
+    let seed = RelevantCoinListSeed { is_relevant: &is_relevant };
+
+    let url = format!(
+        "{}/api/v3/coins/list?include_platform=true",
+        config.coingecko_base_url
+    );
+    let result = get_json_with_retry_with(
         config,
-        url,
+        &url,
         |r| {
             r.header("x-cg-demo-api-key", &config.coingecko_key)
                 .header("Accept", "application/json")
         },
-        5,
-        3,
+        RetryConfig { weight: COIN_LIST_WEIGHT, ..config.retry_config() },
+        Some(&config.coingecko_breaker),
+        Some(&config.coingecko_rate_limiter),
+        |text| {
+            let start = Instant::now();
+            seed.parse(text).map(|list| (list, start.elapsed()))
+        },
     )
     .await;
 
-    let tokens = match result {
-        FetchResult::Success(resp) => resp.as_array().cloned().unwrap_or_default(),
+    let (list, parse_time) = match result {
+        FetchResult::Success(resp) => resp,
         FetchResult::Empty => {
             warn!("⚠️ Token list response empty");
-            return Ok(());
+            return Ok(SyncStats::default());
+        }
+        FetchResult::NotFound => {
+            return Err(anyhow!("Failed to fetch token list: endpoint not found"));
         }
         FetchResult::Failed(e) => {
             return Err(anyhow!("Failed to fetch token list: {}", e));
         }
     };
+    let skipped = list.total - list.entries.len();
+    info!(
+        "📋 Kept {} of {} listed coins with indexed contracts (parsed in {:?})",
+        list.entries.len(),
+        list.total,
+        parse_time
+    );
 
-    let chains_map: HashMap<String, i64> =
-        sqlx::query_as::<_, (String, i64)>("SELECT name, chainid FROM chains")
+    // Pairs already stored are skipped without touching the database
+    let mut existing_pairs: HashSet<(String, i64)> =
+        sqlx::query_as::<_, (String, i64)>("SELECT address, chainid FROM tokenmap")
             .fetch_all(pool)
             .await
-            .context("Failed to load chains")?
+            .context("Failed to load existing tokenmap pairs")?
             .into_iter()
             .collect();
 
-    for token in tokens {
-        let tokenid = token.get("id").and_then(|v| v.as_str());
-        let symbol = token.get("symbol").and_then(|v| v.as_str());
-        let name = token.get("name").and_then(|v| v.as_str());
-        let platforms = token.get("platforms").and_then(|v| v.as_object());
-
-        if tokenid.is_none() || name.is_none() || symbol.is_none() || platforms.is_none() {
-            skipped += 1;
+    let mut new_rows = Vec::new();
+    let mut listed_ids = Vec::with_capacity(list.entries.len());
+    for token in list.entries {
+        let (Some(tokenid), Some(symbol), Some(name), Some(platforms)) =
+            (token.id, token.symbol, token.name, token.platforms)
+        else {
             continue;
-        }
+        };
+        listed_ids.push(tokenid.clone());
 
-        for (platform, address_val) in platforms.unwrap() {
-            let address = address_val.as_str().unwrap_or("").to_lowercase();
-            if address.is_empty() {
-                skipped += 1;
+        for (platform, address_val) in &platforms {
+            let address = address_val.as_deref().unwrap_or("").to_lowercase();
+            let Some(&chainid) = chains_map.get(platform) else {
                 continue;
-            }
+            };
 
-            let Some(chainid) = chains_map.get(platform) else {
+            if !existing_pairs.insert((address.clone(), chainid)) {
                 continue;
-            };
+            }
 
-            let res = sqlx::query(
-                r#"
-                INSERT INTO tokenmap (tokenid, symbol, name, chainid, address)
-                VALUES ($1, $2, $3, $4, $5)
-                ON CONFLICT (address, chainid) DO NOTHING
-                "#,
-            )
-            .bind(tokenid)
-            .bind(symbol)
-            .bind(name)
-            .bind(chainid)
-            .bind(&address)
-            .execute(pool)
-            .await;
+            new_rows.push(MapRow {
+                id: tokenid.clone(),
+                symbol: symbol.clone(),
+                name: name.clone(),
+                chainid,
+                address,
+            });
+        }
+    }
 
-            match res {
-                Ok(_) => inserted += 1,
-                Err(e) => warn!(
-                    "Insert failed for token {}:{} => {}",
-                    tokenid.unwrap(),
-                    address,
-                    e
-                ),
-            }
+    for chunk in new_rows.chunks(TOKENMAP_INSERT_CHUNK) {
+        match insert_map_rows(pool, MapTable::Token, chunk).await {
+            Ok(rows) => inserted += rows as usize,
+            Err(e) => warn!("Bulk insert of {} tokenmap rows failed: {}", chunk.len(), e),
         }
     }
 
+    // Tokens listed again after a 404 are fetched by the metadata sync again
+    match relist_tokens(pool, &listed_ids).await {
+        Ok(0) => {}
+        Ok(rows) => info!("♻️ {} delisted tokenmap entries are listed on CoinGecko again", rows),
+        Err(e) => warn!("Clearing delisted_at of relisted tokens failed: {}", e),
+    }
+
     info!(
         "✅ sync_tokenmap completed: inserted {}, skipped {}",
         inserted, skipped
     );
-    Ok(())
+    Ok(SyncStats {
+        inserted,
+        pages: 1,
+        ..Default::default()
+    })
+}
+
+/// Target of [`insert_map_rows`]
+#[derive(Clone, Copy)]
+enum MapTable {
+    Token,
+    Nft,
+}
+
+/// Inserts map rows in one statement, skipping pairs that already exist
+///
+/// # Returns
+/// * `Ok(n)` - Number of rows actually inserted
+/// * `Err` - Database error (no row of the chunk is inserted)
+async fn insert_map_rows(pool: &PgPool, table: MapTable, rows: &[MapRow]) -> Result<u64> {
+    let mut qb = QueryBuilder::<Postgres>::new(match table {
+        MapTable::Token => "INSERT INTO tokenmap (tokenid, symbol, name, chainid, address) ",
+        MapTable::Nft => "INSERT INTO nftmap (nftid, symbol, name, chainid, address) ",
+    });
+    qb.push_values(rows, |mut b, row| {
+        b.push_bind(&row.id)
+            .push_bind(&row.symbol)
+            .push_bind(&row.name)
+            .push_bind(row.chainid)
+            .push_bind(&row.address);
+    });
+    qb.push(" ON CONFLICT (address, chainid) DO NOTHING");
+
+    let result = qb.build().execute(pool).await?;
+    Ok(result.rows_affected())
 }
 
 // ================== NFTMap 同步 ==================
-pub async fn sync_nftmap(config: &Config) -> Result<()> {
+
+/// Entries per `/nfts/list` page (CoinGecko's maximum)
+const NFT_LIST_PER_PAGE: usize = 250;
+
+/// `/nfts/list` pages fetched concurrently by [`sync_nftmap`]
+const NFT_PAGE_CONCURRENCY: usize = 3;
+
+#[instrument(skip_all)]
+pub async fn sync_nftmap(config: &Config) -> Result<SyncStats> {
     info!("🔄 Syncing nftmap from Coingecko...");
 
     let pool = &config.postgres_db.pool;
-    let mut inserted = 0usize;
     let mut skipped = 0usize;
+    let mut stats = SyncStats::default();
 
     let chains_map: HashMap<String, i64> =
         sqlx::query_as::<_, (String, i64)>("SELECT name, chainid FROM chains")
@@ -120,41 +528,39 @@ pub async fn sync_nftmap(config: &Config) -> Result<()> {
             .into_iter()
             .collect();
 
-    let mut page = 1usize;
-
-    loop {
-        let url = format!(
-            "https://api.coingecko.com/api/v3/nfts/list?per_page=250&page={}",
-            page
-        );
-
-        let result = get_json_with_retry::<Value>(
-            config,
-            &url,
-            |r| {
-                r.header("x-cg-demo-api-key", &config.coingecko_key)
-                    .header("Accept", "application/json")
-            },
-            5,
-            3,
-        )
-        .await;
+    // Pairs already stored are skipped without touching the database
+    let mut existing_pairs: HashSet<(String, i64)> =
+        sqlx::query_as::<_, (String, i64)>("SELECT address, chainid FROM nftmap")
+            .fetch_all(pool)
+            .await
+            .context("Failed to load existing nftmap pairs")?
+            .into_iter()
+            .collect();
 
-        let nfts = match result {
-            FetchResult::Success(resp) => resp.as_array().cloned().unwrap_or_default(),
-            FetchResult::Empty => {
+    // Pages are fetched NFT_PAGE_CONCURRENCY at a time and complete out of
+    // order. The first empty page caps the page numbers handed out, while
+    // pages already in flight are still drained so none before it is lost.
+    // Pacing comes from the shared CoinGecko rate limiter.
+    let end_page = AtomicUsize::new(usize::MAX);
+    let end_page = &end_page;
+    let mut pages = pin!(stream::unfold(1usize, |page| {
+        future::ready((page < end_page.load(Ordering::Relaxed)).then_some((page, page + 1)))
+    })
+    .map(|page| fetch_nft_list_page(config, page).map(move |result| (page, result)))
+    .buffer_unordered(NFT_PAGE_CONCURRENCY));
+
+    while let Some((page, result)) = pages.next().await {
+        let nfts = result?;
+        if nfts.is_empty() {
+            if page < end_page.fetch_min(page, Ordering::Relaxed) {
                 info!("Reached empty NFT list on page {}, stopping.", page);
-                break;
-            }
-            FetchResult::Failed(e) => {
-                return Err(anyhow!("Failed to fetch NFT list: {}", e));
             }
-        };
-
-        if nfts.is_empty() {
-            break;
+            continue;
         }
+        stats.pages += 1;
+        stats.fetched += nfts.len();
 
+        let mut new_rows = Vec::new();
         for nft in &nfts {
             let id = nft.get("id").and_then(|v| v.as_str());
             let name = nft.get("name").and_then(|v| v.as_str());
@@ -170,53 +576,83 @@ pub async fn sync_nftmap(config: &Config) -> Result<()> {
                 .unwrap_or("")
                 .to_string();
 
-            if id.is_none()
-                || name.is_none()
-                || symbol.is_none()
-                || addr.is_empty()
-                || platform.is_empty()
-            {
+            let (Some(id), Some(name), Some(symbol)) = (id, name, symbol) else {
+                skipped += 1;
+                continue;
+            };
+            if addr.is_empty() || platform.is_empty() {
                 skipped += 1;
                 continue;
             }
 
-            let Some(chainid) = chains_map.get(&platform) else {
+            let Some(&chainid) = chains_map.get(&platform) else {
                 skipped += 1;
                 continue;
             };
 
-            let res = sqlx::query(
-                r#"
-                INSERT INTO nftmap (nftid, symbol, name, chainid, address)
-                VALUES ($1, $2, $3, $4, $5)
-                ON CONFLICT (address, chainid) DO NOTHING
-                "#,
-            )
-            .bind(id)
-            .bind(symbol)
-            .bind(name)
-            .bind(chainid)
-            .bind(&addr)
-            .execute(pool)
-            .await;
+            if !existing_pairs.insert((addr.clone(), chainid)) {
+                continue;
+            }
 
-            if res.is_ok() {
-                inserted += 1;
-            } else {
-                skipped += 1;
+            new_rows.push(MapRow {
+                id: id.to_string(),
+                symbol: symbol.to_string(),
+                name: name.to_string(),
+                chainid,
+                address: addr,
+            });
+        }
+
+        if !new_rows.is_empty() {
+            match insert_map_rows(pool, MapTable::Nft, &new_rows).await {
+                Ok(rows) => stats.inserted += rows as usize,
+                Err(e) => {
+                    warn!("Bulk insert of {} nftmap rows failed: {}", new_rows.len(), e);
+                    skipped += new_rows.len();
+                }
             }
         }
 
-        info!("✅ Processed page {}, total inserted {}", page, inserted);
-        page += 1;
-        sleep(Duration::from_millis(300)).await;
+        info!("✅ Processed page {}, total inserted {}", page, stats.inserted);
     }
 
     info!(
-        "✅ sync_nftmap completed: inserted {}, skipped {}",
-        inserted, skipped
+        "✅ sync_nftmap completed: {} NFTs over {} pages, inserted {}, skipped {}",
+        stats.fetched, stats.pages, stats.inserted, skipped
     );
-    Ok(())
+    Ok(stats)
+}
+
+/// Fetches one page of CoinGecko's `/nfts/list`
+///
+/// # Returns
+/// * `Ok(nfts)` - Entries of the page (empty past the last page)
+/// * `Err` - Request failed after retries
+async fn fetch_nft_list_page(config: &Config, page: usize) -> Result<Vec<Value>> {
+    let url = format!(
+        "{}/api/v3/nfts/list?per_page={}&page={}",
+        config.coingecko_base_url, NFT_LIST_PER_PAGE, page
+    );
+
+    let result = get_json_with_retry::<Value>(
+        config,
+        &url,
+        |r| {
+            r.header("x-cg-demo-api-key", &config.coingecko_key)
+                .header("Accept", "application/json")
+        },
+        config.retry_config(),
+        Some(&config.coingecko_breaker),
+        Some(&config.coingecko_rate_limiter),
+    )
+    .await;
+
+    match result {
+        FetchResult::Success(Value::Array(nfts)) => Ok(nfts),
+        FetchResult::Success(_) | FetchResult::Empty => Ok(Vec::new()),
+        FetchResult::NotFound => Err(anyhow!("Failed to fetch NFT list: endpoint not found")),
+        FetchResult::Failed(e) => Err(anyhow!("Failed to fetch NFT list page {}: {}", page, e)),
+    }
 }
 
 // ======================= Metadata Structures =======================
@@ -246,6 +682,18 @@ struct MetadataItem<'a> {
     description: Option<&'a str>,
     /// Additional notices/warnings in JSON format
     notices: Option<Value>,
+    /// Social/contact links in JSON format (twitter, telegram, github, ...)
+    social_links: Option<Value>,
+    /// CoinGecko developer statistics in JSON format (stars, forks, commits, ...)
+    developer_data: Option<Value>,
+    /// Addresses on every chain the token is deployed on (`{platform: address}`)
+    platforms: Option<Value>,
+    /// CoinGecko community counters in JSON format (twitter_followers, reddit_subscribers, ...)
+    community_data: Option<Value>,
+    /// Top exchange tickers by volume in JSON format
+    tickers: Option<Value>,
+    /// Launch date reported by CoinGecko
+    genesis_date: Option<NaiveDate>,
 }
 
 // ======================= Database Operations =======================
@@ -266,9 +714,11 @@ async fn insert_metadata(pool: &PgPool, data: &MetadataItem<'_>) -> Result<()> {
     sqlx::query(
         r#"
         INSERT INTO metadata (
-            tokenid, nftid, symbol, name, chainid, address, decimals, homepage, image, description, notices, created_at
+            tokenid, nftid, symbol, name, chainid, address, decimals, homepage, image, description,
+            notices, social_links, developer_data, platforms, community_data, tickers, genesis_date,
+            refreshed_at, created_at
         )
-        VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,NOW())
+        VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,NOW(),NOW())
         ON CONFLICT (address, chainid) DO NOTHING
         "#,
     )
@@ -283,6 +733,12 @@ async fn insert_metadata(pool: &PgPool, data: &MetadataItem<'_>) -> Result<()> {
     .bind(data.image)
     .bind(data.description)
     .bind(data.notices.as_ref().map(sqlx::types::Json))
+    .bind(data.social_links.as_ref().map(sqlx::types::Json))
+    .bind(data.developer_data.as_ref().map(sqlx::types::Json))
+    .bind(data.platforms.as_ref().map(sqlx::types::Json))
+    .bind(data.community_data.as_ref().map(sqlx::types::Json))
+    .bind(data.tickers.as_ref().map(sqlx::types::Json))
+    .bind(data.genesis_date)
     .execute(pool)
     .await?;
     Ok(())
@@ -295,8 +751,10 @@ async fn insert_metadata(pool: &PgPool, data: &MetadataItem<'_>) -> Result<()> {
 ///
 /// # Update Strategy
 /// - Always update: symbol, name (core identifiers)
-/// - Always update: homepage, image, description, notices (full refresh)
+/// - Always update: homepage, image, description, notices, social_links,
+///   developer_data, platforms, community_data, tickers, genesis_date (full refresh)
 /// - COALESCE is used to preserve non-null old values when new value is NULL
+/// - Symbol/name changes are written to `metadata_history` in the same transaction
 ///
 /// # Arguments
 /// * `pool` - Database connection pool
@@ -305,17 +763,52 @@ async fn insert_metadata(pool: &PgPool, data: &MetadataItem<'_>) -> Result<()> {
 /// # Returns
 /// * `Ok(())` - Insert or update succeeded
 /// * `Err` - Database error occurred
-///
-/// # Note
-/// Currently unused but kept for future monthly force update feature
-#[allow(dead_code)]
 async fn force_update_metadata(pool: &PgPool, data: &MetadataItem<'_>) -> Result<()> {
+    let mut tx = pool.begin().await?;
+
+    // Lock the existing row so concurrent refreshes record each change once
+    let existing: Option<(i32, String, String)> = sqlx::query_as(
+        "SELECT id, symbol, name FROM metadata WHERE address = $1 AND chainid = $2 FOR UPDATE",
+    )
+    .bind(data.address)
+    .bind(data.chainid)
+    .fetch_optional(&mut *tx)
+    .await?;
+
+    if let Some((metadata_id, old_symbol, old_name)) = existing {
+        let tracked = [("symbol", old_symbol, data.symbol), ("name", old_name, data.name)];
+
+        for (field, old, new) in tracked.into_iter().filter(|(_, old, new)| old != new) {
+            sqlx::query(
+                r#"
+                INSERT INTO metadata_history (metadata_id, chainid, address, field, old_value, new_value)
+                VALUES ($1, $2, $3, $4, $5, $6)
+                "#,
+            )
+            .bind(metadata_id)
+            .bind(data.chainid)
+            .bind(data.address)
+            .bind(field)
+            .bind(&old)
+            .bind(new)
+            .execute(&mut *tx)
+            .await?;
+
+            info!(
+                "📝 {} changed for {}:{}: '{}' -> '{}'",
+                field, data.chainid, data.address, old, new
+            );
+        }
+    }
+
     sqlx::query(
         r#"
         INSERT INTO metadata (
-            tokenid, nftid, symbol, name, chainid, address, decimals, homepage, image, description, notices, created_at
+            tokenid, nftid, symbol, name, chainid, address, decimals, homepage, image, description,
+            notices, social_links, developer_data, platforms, community_data, tickers, genesis_date,
+            refreshed_at, created_at
         )
-        VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,NOW())
+        VALUES ($1,$2,$3,$4,$5,$6,$7,$8,$9,$10,$11,$12,$13,$14,$15,$16,$17,NOW(),NOW())
         ON CONFLICT (address, chainid)
         DO UPDATE SET
             symbol = EXCLUDED.symbol,
@@ -324,6 +817,13 @@ async fn force_update_metadata(pool: &PgPool, data: &MetadataItem<'_>) -> Result
             image = COALESCE(EXCLUDED.image, metadata.image),
             description = COALESCE(EXCLUDED.description, metadata.description),
             notices = COALESCE(EXCLUDED.notices, metadata.notices),
+            social_links = COALESCE(EXCLUDED.social_links, metadata.social_links),
+            developer_data = COALESCE(EXCLUDED.developer_data, metadata.developer_data),
+            platforms = COALESCE(EXCLUDED.platforms, metadata.platforms),
+            community_data = COALESCE(EXCLUDED.community_data, metadata.community_data),
+            tickers = COALESCE(EXCLUDED.tickers, metadata.tickers),
+            genesis_date = COALESCE(EXCLUDED.genesis_date, metadata.genesis_date),
+            refreshed_at = NOW(),
             updated_at = NOW()
         "#,
     )
@@ -338,30 +838,77 @@ async fn force_update_metadata(pool: &PgPool, data: &MetadataItem<'_>) -> Result
     .bind(data.image)
     .bind(data.description)
     .bind(data.notices.as_ref().map(sqlx::types::Json))
-    .execute(pool)
+    .bind(data.social_links.as_ref().map(sqlx::types::Json))
+    .bind(data.developer_data.as_ref().map(sqlx::types::Json))
+    .bind(data.platforms.as_ref().map(sqlx::types::Json))
+    .bind(data.community_data.as_ref().map(sqlx::types::Json))
+    .bind(data.tickers.as_ref().map(sqlx::types::Json))
+    .bind(data.genesis_date)
+    .execute(&mut *tx)
     .await?;
+
+    tx.commit().await?;
     Ok(())
 }
 
+/// Fetches `/coins/{id}` through the CoinGecko breaker and limiter
+///
+/// Tickers are requested only with `FETCH_TICKERS` (and otherwise dropped,
+/// so stored ones are kept); market data and sparklines never are (they
+/// come from the market data sync).
+async fn fetch_coin_detail(config: &Config, token_id: &str) -> FetchResult<CoinDetail> {
+    let url = format!("{}/api/v3/coins/{}", config.coingecko_base_url, token_id);
+    let result = get_json_with_retry::<CoinDetail>(
+        config,
+        &url,
+        |r| {
+            r.header("x-cg-demo-api-key", &config.coingecko_key)
+                .header("Accept", "application/json")
+                .query(&[
+                    ("localization", "false"),
+                    ("tickers", if config.fetch_tickers { "true" } else { "false" }),
+                    ("market_data", "false"),
+                    ("developer_data", "true"),
+                    ("sparkline", "false"),
+                ])
+        },
+        config.retry_config(),
+        Some(&config.coingecko_breaker),
+        Some(&config.coingecko_rate_limiter),
+    )
+    .await;
+
+    match result {
+        FetchResult::Success(mut resp) if !config.fetch_tickers => {
+            resp.tickers = None;
+            FetchResult::Success(resp)
+        }
+        other => other,
+    }
+}
+
 // ======================= Daily Incremental Sync =======================
 
 /// Daily incremental sync of token metadata from CoinGecko
 ///
 /// This function fetches metadata only for NEW tokens that don't exist in the metadata table yet.
-/// It skips tokens that already have metadata to minimize API calls and respect rate limits.
+/// It skips tokens that already have metadata to minimize API calls and respect rate limits;
+/// those are re-checked by [`refresh_token_metadata`] instead.
 ///
 /// # Workflow
-/// 1. Load tokens from tokenmap (where id > last_update_id)
+/// 1. Load tokens from tokenmap (where id > last_update_id), keeping only
+///    tokens whose `marketdata` market cap reaches `METADATA_MIN_MARKET_CAP_USD`
+///    (plus tokens without one if `METADATA_INCLUDE_MISSING_MARKET_CAP`)
 /// 2. For each token, check if metadata already exists
-/// 3. If exists, skip (to save API calls)
-/// 4. If not exists, fetch from CoinGecko API and insert
+/// 3. If exists, skip (to save API calls) unless the token is on the watchlist
+/// 4. Otherwise fetch from CoinGecko API and insert (watchlisted tokens overwrite)
 /// 5. Update config: set token_update_id to max_id on failure, 0 on success
 ///
 /// # Arguments
 /// * `config` - Mutable application configuration (for updating token_update_id)
 ///
 /// # Returns
-/// * `Ok(())` - All tokens processed successfully (config.token_update_id set to 0)
+/// * `Ok(SyncStats)` - All tokens processed successfully (config.token_update_id set to 0)
 /// * `Err` - Fatal error (database connection, API failure, etc.; config.token_update_id set to max_id)
 ///
 /// # Performance
@@ -371,88 +918,107 @@ async fn force_update_metadata(pool: &PgPool, data: &MetadataItem<'_>) -> Result
 ///
 /// # Side Effects
 /// - Updates config.token_update_id: 0 on completion, max_id on interruption
-pub async fn fetch_token_metadata(config: &mut Config) -> Result<()> {
+#[instrument(skip_all)]
+pub async fn fetch_token_metadata(config: &mut Config) -> Result<SyncStats> {
     let pool = &config.postgres_db.pool;
 
     // Start from last processed token ID (for incremental processing)
     let last_update_id = config.token_update_id;
 
-    let tokenmap: Vec<(i64, String, String, i64, String)> = sqlx::query_as(
-        "SELECT id, tokenid, name, chainid, address FROM tokenmap WHERE id > $1 ORDER BY id ASC",
+    // Dust tokens are left out to spend the API budget on relevant ones
+    if let Some(min) = config.metadata_min_market_cap_usd {
+        info!(
+            "Fetching metadata only for tokens with market cap >= ${} (include missing: {})",
+            min, config.metadata_include_missing_market_cap
+        );
+    }
+    let tokenmap: Vec<(i64, String, String, i64, String, bool)> = sqlx::query_as(
+        r#"
+        SELECT t.id, t.tokenid, t.name, t.chainid, t.address, w.id IS NOT NULL AS watched
+        FROM tokenmap t
+        LEFT JOIN watchlist w ON w.address = t.address AND w.chainid = t.chainid
+        LEFT JOIN (
+            SELECT token_id, MAX(market_cap) AS market_cap
+            FROM marketdata
+            GROUP BY token_id
+        ) md ON md.token_id = t.tokenid
+        WHERE t.id > $1
+            AND t.delisted_at IS NULL
+            AND (
+                $2::FLOAT8 IS NULL
+                OR w.id IS NOT NULL
+                OR md.market_cap >= $2
+                OR ($3 AND md.market_cap IS NULL)
+            )
+        ORDER BY t.id ASC
+        "#,
     )
     .bind(last_update_id)
+    .bind(config.metadata_min_market_cap_usd)
+    .bind(config.metadata_include_missing_market_cap)
     .fetch_all(pool)
     .await
     .context("Failed to load tokenmap for metadata")?;
 
-    let mut max_id = last_update_id;
     let mut inserted = 0usize;
+    let mut updated = 0usize;
+    let mut deactivated = 0usize;
+    let mut delisted = 0usize;
     let total = tokenmap.len();
 
-    for (i, (id, token_id, _name, chainid, address)) in tokenmap.into_iter().enumerate() {
-        max_id = id; // Track current max ID for resume capability
+    for (i, (id, token_id, _name, chainid, address, watched)) in tokenmap.into_iter().enumerate() {
+        let max_id = id; // Track current max ID for resume capability
 
-        // Skip tokens that already have metadata (daily sync only adds new ones)
-        if config
-            .postgres_db
-            .contract_exists(&address, chainid)
-            .await
-            .unwrap_or(false)
+        // Skip tokens that already have metadata (daily sync only adds new
+        // ones), except watchlisted tokens which are refreshed every run
+        if !watched
+            && config
+                .postgres_db
+                .contract_exists(&address, chainid)
+                .await
+                .unwrap_or(false)
         {
             continue; // Metadata exists, skip to save API calls
         }
 
-        let url = format!("https://api.coingecko.com/api/v3/coins/{}", token_id);
-        let result = get_json_with_retry::<Value>(
-            config,
-            &url,
-            |r| {
-                r.header("x-cg-demo-api-key", &config.coingecko_key)
-                    .header("Accept", "application/json")
-                    .query(&[
-                        ("localization", "false"),
-                        ("tickers", "false"),
-                        ("market_data", "false"),
-                        ("developer_data", "false"),
-                        ("sparkline", "false"),
-                    ])
-            },
-            5,
-            3,
-        )
-        .await;
-
-        match result {
+        match fetch_coin_detail(config, &token_id).await {
             FetchResult::Success(resp) => {
-                let tokenid = resp.get("id").and_then(|v| v.as_str()).unwrap_or("");
-                let symbol = resp.get("symbol").and_then(|v| v.as_str()).unwrap_or("");
-                let name = resp.get("name").and_then(|v| v.as_str()).unwrap_or("");
-                if symbol.is_empty() || name.is_empty() {
+                let Some((symbol, name)) = resp.symbol_and_name() else {
                     warn!("Skipping token {} with empty symbol/name", token_id);
                     continue;
-                }
-
-                let homepage = resp.pointer("/links/homepage/0").and_then(|v| v.as_str());
-                let image = resp.pointer("/image/large").and_then(|v| v.as_str());
-                let description = resp.pointer("/description/en").and_then(|v| v.as_str());
-                let notices = resp.get("additional_notices").cloned();
+                };
 
                 let data = MetadataItem {
-                    tokenid: Some(tokenid),
+                    tokenid: Some(&resp.id),
                     nftid: None,
                     symbol,
                     name,
                     chainid,
                     address: &address,
                     decimals: None,
-                    homepage,
-                    image,
-                    description,
-                    notices,
+                    homepage: resp.homepage(),
+                    image: resp.image(),
+                    description: resp.description(),
+                    notices: resp.additional_notices.clone(),
+                    social_links: resp.social_links(),
+                    developer_data: resp.developer_data(),
+                    platforms: resp.platforms(),
+                    community_data: resp.community_data(),
+                    tickers: resp.tickers(),
+                    genesis_date: resp.genesis_date(),
                 };
 
-                // Insert new metadata (will skip if conflict due to race condition)
-                match insert_metadata(pool, &data).await {
+                // Insert new metadata (will skip if conflict due to race condition);
+                // watchlisted tokens overwrite what is stored
+                let write = retry_db("metadata insert", DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, || async {
+                    if watched {
+                        force_update_metadata(pool, &data).await
+                    } else {
+                        insert_metadata(pool, &data).await
+                    }
+                });
+                match write.await {
+                    Ok(_) if watched => updated += 1,
                     Ok(_) => {
                         inserted += 1;
                     }
@@ -464,6 +1030,22 @@ pub async fn fetch_token_metadata(config: &mut Config) -> Result<()> {
                 warn!("⚠️ Token {} returned empty response", token_id);
             }
 
+            // Delisted, merged or deprecated on CoinGecko: hide and stop fetching it,
+            // don't fail the run
+            FetchResult::NotFound => {
+                match deactivate_metadata(pool, &token_id).await {
+                    Ok(rows) => {
+                        deactivated += rows as usize;
+                        warn!("🚫 Token {} not found on CoinGecko, {} metadata row(s) deactivated", token_id, rows);
+                    }
+                    Err(e) => warn!("Deactivation failed for token {}: {}", token_id, e),
+                }
+                match delist_token(pool, &token_id).await {
+                    Ok(rows) => delisted += rows as usize,
+                    Err(e) => warn!("Delisting failed for token {}: {}", token_id, e),
+                }
+            }
+
             FetchResult::Failed(e) => {
                 warn!(
                     "❌ Failed to fetch token {} ({}/{}): {}",
@@ -482,12 +1064,205 @@ pub async fn fetch_token_metadata(config: &mut Config) -> Result<()> {
     }
 
     info!(
-        "✅ Daily token metadata sync completed: {} new tokens inserted",
-        inserted
+        "✅ Daily token metadata sync completed: {} new tokens inserted, {} watchlisted refreshed, {} rows deactivated, {} tokenmap entries delisted",
+        inserted, updated, deactivated, delisted
+    );
+    
+    // Reset to 0 to indicate full completion (next run starts from beginning)
+    config.set_token_update_id(0);
+    Ok(SyncStats {
+        inserted,
+        updated,
+        deactivated,
+        delisted_count: delisted,
+        ..Default::default()
+    })
+}
+
+/// Hides the metadata of a CoinGecko token ID that no longer exists
+///
+/// Rows are kept (and can be restored with the `reactivate_metadata` RPC);
+/// the read API skips inactive rows by default. `updated_at` is bumped so
+/// cached token details (ETag) are revalidated.
+///
+/// # Returns
+/// Number of rows deactivated
+async fn deactivate_metadata(pool: &PgPool, tokenid: &str) -> Result<u64> {
+    let result = sqlx::query(
+        "UPDATE metadata SET is_active = false, refreshed_at = NOW(), updated_at = NOW() WHERE tokenid = $1 AND is_active",
+    )
+        .bind(tokenid)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Marks the `tokenmap` entries of a CoinGecko token ID as delisted
+///
+/// The metadata sync skips delisted entries until [`sync_tokenmap`] sees
+/// the ID in the coin list again.
+///
+/// # Returns
+/// Number of entries delisted
+async fn delist_token(pool: &PgPool, tokenid: &str) -> Result<u64> {
+    let result = sqlx::query("UPDATE tokenmap SET delisted_at = NOW() WHERE tokenid = $1 AND delisted_at IS NULL")
+        .bind(tokenid)
+        .execute(pool)
+        .await?;
+    Ok(result.rows_affected())
+}
+
+/// Clears `delisted_at` of entries whose token ID is in the current coin list
+///
+/// # Returns
+/// Number of entries relisted
+async fn relist_tokens(pool: &PgPool, tokenids: &[String]) -> Result<u64> {
+    let result =
+        sqlx::query("UPDATE tokenmap SET delisted_at = NULL WHERE delisted_at IS NOT NULL AND tokenid = ANY($1)")
+            .bind(tokenids)
+            .execute(pool)
+            .await?;
+    Ok(result.rows_affected())
+}
+
+// ======================= Daily Refresh =======================
+
+/// Re-checks stored token metadata on CoinGecko, oldest first
+///
+/// [`fetch_token_metadata`] never looks at tokens that already have
+/// metadata, so without this a token that CoinGecko drops stays active
+/// forever and its fields never change. Each run takes up to
+/// `METADATA_REFRESH_BATCH` active token IDs not re-checked within
+/// `METADATA_REFRESH_DAYS` (never-checked rows first) and:
+/// - on success, overwrites every row of the token via [`force_update_metadata`]
+/// - on a 404, deactivates the rows and delists the `tokenmap` entries like
+///   the daily sync does
+///
+/// `refreshed_at` is stamped per token, so an interrupted run resumes with
+/// the tokens it did not reach. Rows stored before a field existed (e.g.
+/// `platforms`) are never-checked, so the refresh also backfills them.
+///
+/// # Returns
+/// * `Ok(SyncStats)` - Rows updated and deactivated (empty if disabled)
+/// * `Err` - Database error or CoinGecko failure
+#[instrument(skip_all)]
+pub async fn refresh_token_metadata(config: &Config) -> Result<SyncStats> {
+    if config.metadata_refresh_days == 0 {
+        return Ok(SyncStats::default());
+    }
+    let pool = &config.postgres_db.pool;
+
+    let due: Vec<String> = sqlx::query_scalar(
+        r#"
+        SELECT tokenid
+        FROM metadata
+        WHERE tokenid IS NOT NULL
+            AND is_active
+            AND (refreshed_at IS NULL OR refreshed_at < NOW() - make_interval(days => $1))
+        GROUP BY tokenid
+        ORDER BY MIN(refreshed_at) ASC NULLS FIRST
+        LIMIT $2
+        "#,
+    )
+    .bind(config.metadata_refresh_days as i32)
+    .bind(config.metadata_refresh_batch)
+    .fetch_all(pool)
+    .await
+    .context("Failed to load metadata due for refresh")?;
+
+    let mut updated = 0usize;
+    let mut deactivated = 0usize;
+    let mut delisted = 0usize;
+
+    for token_id in &due {
+        match fetch_coin_detail(config, token_id).await {
+            FetchResult::Success(resp) => {
+                let Some((symbol, name)) = resp.symbol_and_name() else {
+                    warn!("Skipping token {} with empty symbol/name", token_id);
+                    touch_refreshed(pool, token_id).await?;
+                    continue;
+                };
+
+                let rows: Vec<(i64, String)> =
+                    sqlx::query_as("SELECT chainid, address FROM metadata WHERE tokenid = $1")
+                        .bind(token_id)
+                        .fetch_all(pool)
+                        .await?;
+
+                for (chainid, address) in &rows {
+                    let data = MetadataItem {
+                        tokenid: Some(&resp.id),
+                        nftid: None,
+                        symbol,
+                        name,
+                        chainid: *chainid,
+                        address,
+                        decimals: None,
+                        homepage: resp.homepage(),
+                        image: resp.image(),
+                        description: resp.description(),
+                        notices: resp.additional_notices.clone(),
+                        social_links: resp.social_links(),
+                        developer_data: resp.developer_data(),
+                        platforms: resp.platforms(),
+                        community_data: resp.community_data(),
+                        tickers: resp.tickers(),
+                        genesis_date: resp.genesis_date(),
+                    };
+                    let write = retry_db("metadata refresh", DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, || {
+                        force_update_metadata(pool, &data)
+                    });
+                    match write.await {
+                        Ok(()) => updated += 1,
+                        Err(e) => warn!("Refresh failed for token {} on chain {}: {}", token_id, chainid, e),
+                    }
+                }
+            }
+
+            FetchResult::Empty => {
+                warn!("⚠️ Token {} returned empty response", token_id);
+                touch_refreshed(pool, token_id).await?;
+            }
+
+            // Gone from CoinGecko since it was first fetched
+            FetchResult::NotFound => {
+                let rows = deactivate_metadata(pool, token_id).await?;
+                deactivated += rows as usize;
+                warn!("🚫 Token {} not found on CoinGecko, {} metadata row(s) deactivated", token_id, rows);
+                delisted += delist_token(pool, token_id).await? as usize;
+            }
+
+            FetchResult::Failed(e) => {
+                return Err(anyhow!("API request failed for token {}: {}", token_id, e));
+            }
+        }
+
+        sleep(Duration::from_millis(300)).await;
+    }
+
+    info!(
+        "✅ Token metadata refresh completed: {} tokens re-checked, {} rows updated, {} rows deactivated, {} tokenmap entries delisted",
+        due.len(),
+        updated,
+        deactivated,
+        delisted
     );
-    
-    // Reset to 0 to indicate full completion (next run starts from beginning)
-    config.set_token_update_id(0);
+    Ok(SyncStats {
+        updated,
+        deactivated,
+        delisted_count: delisted,
+        ..Default::default()
+    })
+}
+
+/// Stamps `refreshed_at` of a token whose re-check left nothing to store
+///
+/// Keeps such tokens from holding the head of the refresh queue.
+async fn touch_refreshed(pool: &PgPool, tokenid: &str) -> Result<()> {
+    sqlx::query("UPDATE metadata SET refreshed_at = NOW() WHERE tokenid = $1")
+        .bind(tokenid)
+        .execute(pool)
+        .await?;
     Ok(())
 }
 
@@ -520,6 +1295,7 @@ pub async fn fetch_token_metadata(config: &mut Config) -> Result<()> {
 /// # Returns
 /// * `Ok(())` - All tokens updated
 /// * `Err` - Error occurred
+#[instrument(skip_all)]
 pub async fn force_update_all_token_metadata(config: &Config) -> Result<()> {
     let pool = &config.postgres_db.pool;
 
@@ -542,8 +1318,8 @@ pub async fn force_update_all_token_metadata(config: &Config) -> Result<()> {
     for (i, (id, token_id, _name, chainid, address)) in tokenmap.into_iter().enumerate() {
         // NO skip check - force update all tokens
 
-        let url = format!("https://api.coingecko.com/api/v3/coins/{}", token_id);
-        let result = get_json_with_retry::<Value>(
+        let url = format!("{}/api/v3/coins/{}", config.coingecko_base_url, token_id);
+        let result = get_json_with_retry::<CoinDetail>(
             config,
             &url,
             |r| {
@@ -551,50 +1327,51 @@ pub async fn force_update_all_token_metadata(config: &Config) -> Result<()> {
                     .header("Accept", "application/json")
                     .query(&[
                         ("localization", "false"),
-                        ("tickers", "false"),
+                        ("tickers", if config.fetch_tickers { "true" } else { "false" }),
                         ("market_data", "false"),
-                        ("developer_data", "false"),
+                        ("developer_data", "true"),
                         ("sparkline", "false"),
                     ])
             },
-            5,
-            3,
+            config.retry_config(),
+            Some(&config.coingecko_breaker),
+            Some(&config.coingecko_rate_limiter),
         )
         .await;
 
         match result {
             FetchResult::Success(resp) => {
-                let tokenid = resp.get("id").and_then(|v| v.as_str()).unwrap_or("");
-                let symbol = resp.get("symbol").and_then(|v| v.as_str()).unwrap_or("");
-                let name = resp.get("name").and_then(|v| v.as_str()).unwrap_or("");
-                
-                if symbol.is_empty() || name.is_empty() {
+                let Some((symbol, name)) = resp.symbol_and_name() else {
                     warn!("Skipping token {} with empty symbol/name", token_id);
                     failed += 1;
                     continue;
-                }
-
-                let homepage = resp.pointer("/links/homepage/0").and_then(|v| v.as_str());
-                let image = resp.pointer("/image/large").and_then(|v| v.as_str());
-                let description = resp.pointer("/description/en").and_then(|v| v.as_str());
-                let notices = resp.get("additional_notices").cloned();
+                };
 
                 let data = MetadataItem {
-                    tokenid: Some(tokenid),
+                    tokenid: Some(&resp.id),
                     nftid: None,
                     symbol,
                     name,
                     chainid,
                     address: &address,
                     decimals: None,
-                    homepage,
-                    image,
-                    description,
-                    notices,
+                    homepage: resp.homepage(),
+                    image: resp.image(),
+                    description: resp.description(),
+                    notices: resp.additional_notices.clone(),
+                    social_links: resp.social_links(),
+                    developer_data: resp.developer_data(),
+                    platforms: resp.platforms(),
+                    community_data: resp.community_data(),
+                    tickers: resp.tickers(),
+                    genesis_date: resp.genesis_date(),
                 };
 
                 // Force update using upsert
-                match force_update_metadata(pool, &data).await {
+                let write = retry_db("metadata upsert", DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, || {
+                    force_update_metadata(pool, &data)
+                });
+                match write.await {
                     Ok(_) => {
                         updated += 1;
                     }
@@ -656,12 +1433,13 @@ pub async fn force_update_all_token_metadata(config: &Config) -> Result<()> {
 /// * `config` - Mutable application configuration (for updating nft_update_id)
 ///
 /// # Returns
-/// * `Ok(())` - All NFTs processed successfully (config.nft_update_id set to 0)
+/// * `Ok(SyncStats)` - All NFTs processed successfully (config.nft_update_id set to 0)
 /// * `Err` - Fatal error (database connection, API failure, etc.; config.nft_update_id set to max_id)
 ///
 /// # Side Effects
 /// - Updates config.nft_update_id: 0 on completion, max_id on interruption
-pub async fn fetch_nft_metadata(config: &mut Config) -> Result<()> {
+#[instrument(skip_all)]
+pub async fn fetch_nft_metadata(config: &mut Config) -> Result<SyncStats> {
     let pool = &config.postgres_db.pool;
 
     let last_update_id = config.nft_update_id;
@@ -674,12 +1452,11 @@ pub async fn fetch_nft_metadata(config: &mut Config) -> Result<()> {
     .await
     .context("Failed to load nftmap")?;
 
-    let mut max_id = last_update_id;
     let mut inserted = 0usize;
     let total = nftmap.len();
 
     for (i, (id, nft_id, _name, chainid, address)) in nftmap.into_iter().enumerate() {
-        max_id = id;
+        let max_id = id;
 
         // Skip NFTs that already have metadata (daily sync only adds new ones)
         if config
@@ -691,7 +1468,7 @@ pub async fn fetch_nft_metadata(config: &mut Config) -> Result<()> {
             continue; // Metadata exists, skip to save API calls
         }
 
-        let url = format!("https://api.coingecko.com/api/v3/nfts/{}", nft_id);
+        let url = format!("{}/api/v3/nfts/{}", config.coingecko_base_url, nft_id);
         let result = get_json_with_retry::<Value>(
             config,
             &url,
@@ -699,8 +1476,9 @@ pub async fn fetch_nft_metadata(config: &mut Config) -> Result<()> {
                 r.header("x-cg-demo-api-key", &config.coingecko_key)
                     .header("Accept", "application/json")
             },
-            5,
-            3,
+            config.retry_config(),
+            Some(&config.coingecko_breaker),
+            Some(&config.coingecko_rate_limiter),
         )
         .await;
 
@@ -729,10 +1507,19 @@ pub async fn fetch_nft_metadata(config: &mut Config) -> Result<()> {
                     image,
                     description,
                     notices: None,
+                    social_links: None,
+                    developer_data: None,
+                    platforms: None,
+                    community_data: None,
+                    tickers: None,
+                    genesis_date: None,
                 };
 
                 // Insert new NFT metadata
-                match insert_metadata(pool, &data).await {
+                let write = retry_db("metadata insert", DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, || {
+                    insert_metadata(pool, &data)
+                });
+                match write.await {
                     Ok(_) => {
                         inserted += 1;
                     }
@@ -744,6 +1531,10 @@ pub async fn fetch_nft_metadata(config: &mut Config) -> Result<()> {
                 warn!("⚠️ NFT {} returned empty response", nft_id);
             }
 
+            FetchResult::NotFound => {
+                warn!("⚠️ NFT {} not found on CoinGecko, skipping", nft_id);
+            }
+
             FetchResult::Failed(e) => {
                 warn!(
                     "❌ Failed to fetch NFT {} ({}/{}): {}",
@@ -768,7 +1559,10 @@ pub async fn fetch_nft_metadata(config: &mut Config) -> Result<()> {
     
     // Reset to 0 to indicate full completion (next run starts from beginning)
     config.set_nft_update_id(0);
-    Ok(())
+    Ok(SyncStats {
+        inserted,
+        ..Default::default()
+    })
 }
 
 /*
@@ -786,6 +1580,7 @@ pub async fn fetch_nft_metadata(config: &mut Config) -> Result<()> {
 /// # Returns
 /// * `Ok(())` - All NFTs updated
 /// * `Err` - Error occurred
+#[instrument(skip_all)]
 pub async fn force_update_all_nft_metadata(config: &Config) -> Result<()> {
     let pool = &config.postgres_db.pool;
 
@@ -808,7 +1603,7 @@ pub async fn force_update_all_nft_metadata(config: &Config) -> Result<()> {
     for (i, (id, nft_id, _name, chainid, address)) in nftmap.into_iter().enumerate() {
         // NO skip check - force update all NFTs
 
-        let url = format!("https://api.coingecko.com/api/v3/nfts/{}", nft_id);
+        let url = format!("{}/api/v3/nfts/{}", config.coingecko_base_url, nft_id);
         let result = get_json_with_retry::<Value>(
             config,
             &url,
@@ -816,8 +1611,9 @@ pub async fn force_update_all_nft_metadata(config: &Config) -> Result<()> {
                 r.header("x-cg-demo-api-key", &config.coingecko_key)
                     .header("Accept", "application/json")
             },
-            5,
-            3,
+            config.retry_config(),
+            Some(&config.coingecko_breaker),
+            Some(&config.coingecko_rate_limiter),
         )
         .await;
 
@@ -848,10 +1644,19 @@ pub async fn force_update_all_nft_metadata(config: &Config) -> Result<()> {
                     image,
                     description,
                     notices: None,
+                    social_links: None,
+                    developer_data: None,
+                    platforms: None,
+                    community_data: None,
+                    tickers: None,
+                    genesis_date: None,
                 };
 
                 // Force update using upsert
-                match force_update_metadata(pool, &data).await {
+                let write = retry_db("metadata upsert", DB_RETRY_ATTEMPTS, DB_RETRY_BACKOFF, || {
+                    force_update_metadata(pool, &data)
+                });
+                match write.await {
                     Ok(_) => {
                         updated += 1;
                     }
@@ -896,6 +1701,135 @@ pub async fn force_update_all_nft_metadata(config: &Config) -> Result<()> {
 }
 */
 
+// ======================= NFT Market Data =======================
+
+/// Rows of `nftmap` loaded per page by [`sync_nft_market_data`]
+const NFTMAP_PAGE_SIZE: i64 = 500;
+
+/// USD market metrics of an NFT collection from CoinGecko `/nfts/{id}`
+#[derive(Debug, Default, PartialEq)]
+struct NftMarketData {
+    floor_price_usd: Option<f64>,
+    volume_24h_usd: Option<f64>,
+    market_cap_usd: Option<f64>,
+}
+
+impl NftMarketData {
+    /// Extracts `floor_price.usd`, `volume_24h.usd` and `market_cap.usd`
+    fn from_detail(resp: &Value) -> Self {
+        let usd = |field: &str| resp.get(field).and_then(|v| v.get("usd")).and_then(Value::as_f64);
+        NftMarketData {
+            floor_price_usd: usd("floor_price"),
+            volume_24h_usd: usd("volume_24h"),
+            market_cap_usd: usd("market_cap"),
+        }
+    }
+}
+
+/// Daily sync of NFT collection market data from CoinGecko
+///
+/// # Workflow
+/// 1. Page through `nftmap` by id (`NFTMAP_PAGE_SIZE` rows at a time)
+/// 2. Fetch `/api/v3/nfts/{id}` once per collection (an nftid can map to
+///    several contracts)
+/// 3. Upsert floor price, 24h volume and market cap into `nft_market_data`
+///
+/// # Arguments
+/// * `config` - Application configuration
+///
+/// # Returns
+/// * `Ok(SyncStats)` - `updated` counts upserted collections
+/// * `Err` - Database error or CoinGecko request failure (the next run starts over)
+#[instrument(skip_all)]
+pub async fn sync_nft_market_data(config: &Config) -> Result<SyncStats> {
+    let pool = &config.postgres_db.pool;
+    let mut stats = SyncStats::default();
+    let mut seen = HashSet::new();
+    let mut last_id = 0i64;
+
+    loop {
+        let page: Vec<(i64, String)> =
+            sqlx::query_as("SELECT id, nftid FROM nftmap WHERE id > $1 ORDER BY id ASC LIMIT $2")
+                .bind(last_id)
+                .bind(NFTMAP_PAGE_SIZE)
+                .fetch_all(pool)
+                .await
+                .context("Failed to load nftmap")?;
+        let Some((max_id, _)) = page.last() else {
+            break;
+        };
+        last_id = *max_id;
+        stats.pages += 1;
+
+        for (_, nft_id) in page {
+            if !seen.insert(nft_id.clone()) {
+                continue; // Same collection on another chain
+            }
+
+            let url = format!("{}/api/v3/nfts/{}", config.coingecko_base_url, nft_id);
+            let result = get_json_with_retry::<Value>(
+                config,
+                &url,
+                |r| {
+                    r.header("x-cg-demo-api-key", &config.coingecko_key)
+                        .header("Accept", "application/json")
+                },
+                config.retry_config(),
+                Some(&config.coingecko_breaker),
+                Some(&config.coingecko_rate_limiter),
+            )
+            .await;
+
+            match result {
+                FetchResult::Success(resp) => {
+                    let data = NftMarketData::from_detail(&resp);
+                    match upsert_nft_market_data(pool, &nft_id, &data).await {
+                        Ok(()) => stats.updated += 1,
+                        Err(e) => warn!("Market data upsert failed for NFT {}: {}", nft_id, e),
+                    }
+                }
+                FetchResult::Empty => {
+                    warn!("⚠️ NFT {} returned empty response", nft_id);
+                }
+                FetchResult::NotFound => {
+                    warn!("⚠️ NFT {} not found on CoinGecko, skipping", nft_id);
+                }
+                FetchResult::Failed(e) => {
+                    return Err(anyhow!("API request failed for NFT {}: {}", nft_id, e));
+                }
+            }
+        }
+    }
+
+    info!(
+        "✅ NFT market data sync completed: {} collections updated",
+        stats.updated
+    );
+    Ok(stats)
+}
+
+/// Inserts or refreshes the market data row of one NFT collection
+async fn upsert_nft_market_data(pool: &PgPool, nft_id: &str, data: &NftMarketData) -> Result<()> {
+    sqlx::query(
+        r#"
+        INSERT INTO nft_market_data (nftid, floor_price_usd, volume_24h_usd, market_cap_usd, updated_at)
+        VALUES ($1, $2::NUMERIC, $3::NUMERIC, $4::NUMERIC, NOW())
+        ON CONFLICT (nftid) DO UPDATE SET
+            floor_price_usd = EXCLUDED.floor_price_usd,
+            volume_24h_usd = EXCLUDED.volume_24h_usd,
+            market_cap_usd = EXCLUDED.market_cap_usd,
+            updated_at = NOW()
+        "#,
+    )
+    .bind(nft_id)
+    .bind(data.floor_price_usd)
+    .bind(data.volume_24h_usd)
+    .bind(data.market_cap_usd)
+    .execute(pool)
+    .await?;
+    Ok(())
+}
+
 // ======================= Blockscout Metadata Enhancement =======================
 
 /// Partial metadata structure for Blockscout updates
@@ -905,7 +1839,7 @@ pub async fn force_update_all_nft_metadata(config: &Config) -> Result<()> {
 #[derive(Debug, sqlx::FromRow)]
 struct MetadataPartial {
     /// Database row ID
-    id: i64,
+    id: i32,
     /// Blockchain chain ID
     chainid: i64,
     /// Contract address (lowercase hex)
@@ -914,8 +1848,22 @@ struct MetadataPartial {
     token_type: Option<String>,
     /// Whether contract source code is verified
     is_verified: Option<bool>,
-    /// Risk level assessment (e.g., "scam")
+    /// Risk level bucket (low/medium/high)
     risk_level: Option<String>,
+    /// Whether the holder count was never checked or is older than [`HOLDER_COUNT_REFRESH_DAYS`]
+    holders_due: bool,
+    /// Whether the contract is a proxy
+    is_proxy: Option<bool>,
+    /// Whether the proxy's implementation contract is verified
+    implementation_verified: Option<bool>,
+    /// Whether the implementation lookup was never attempted or is older than [`IMPLEMENTATION_RETRY_DAYS`]
+    implementation_due: bool,
+    /// Whether any of homepage/image/description is present
+    has_metadata: bool,
+    /// Whether `contract_abis` has no ABI for the address yet
+    missing_abi: bool,
+    /// Market cap from `marketdata` (None if the token is not listed)
+    market_cap: Option<f64>,
 }
 
 /// Blockscout API response structure
@@ -936,6 +1884,125 @@ struct BlockscoutResponse {
     /// Token-specific information (if address is a token contract)
     #[serde(default)]
     token: Option<TokenInfo>,
+    /// Implementation contract when the address is a proxy
+    #[serde(default)]
+    implementation_address: Option<String>,
+    /// Implementation contracts (newer Blockscout versions report a list)
+    #[serde(default)]
+    implementations: Option<Vec<ProxyImplementation>>,
+}
+
+/// Entry of Blockscout's `implementations` list
+#[derive(Debug, Deserialize)]
+struct ProxyImplementation {
+    /// Implementation contract address
+    #[serde(default, alias = "address_hash")]
+    address: Option<String>,
+}
+
+impl BlockscoutResponse {
+    /// Proxy implementation address (lowercase), from either response format
+    fn proxy_implementation(&self) -> Option<String> {
+        self.implementation_address
+            .as_deref()
+            .or_else(|| {
+                self.implementations
+                    .as_ref()?
+                    .iter()
+                    .find_map(|i| i.address.as_deref())
+            })
+            .filter(|a| !a.is_empty())
+            .map(str::to_lowercase)
+    }
+}
+
+/// Looks up whether a proxy's implementation contract is verified on Blockscout
+///
+/// Best effort: `None` if the request fails or Blockscout does not know
+/// the address; the lookup is then retried after [`IMPLEMENTATION_RETRY_DAYS`].
+async fn implementation_verified(
+    config: &Config,
+    base_url: &str,
+    chainid: i64,
+    implementation: &str,
+) -> Option<bool> {
+    let url = format!("{}/{}", base_url.trim_end_matches('/'), implementation);
+    match fetch_blockscout_pages(config, &url, chainid).await {
+        Ok(items) => items.first().map(|data| data.is_verified),
+        Err(e) => {
+            warn!("⚠️ [chainid={}] Implementation lookup failed for {}: {:#}", chainid, implementation, e);
+            None
+        }
+    }
+}
+
+/// Blockscout `/api/v2/smart-contracts/{address}` response (only the ABI)
+#[derive(Debug, Deserialize)]
+struct SmartContractResponse {
+    /// Contract ABI (absent for unverified contracts)
+    #[serde(default)]
+    abi: Option<Value>,
+}
+
+/// Builds the smart-contract URL from a configured `/api/v2/addresses` endpoint
+///
+/// # Returns
+/// `None` if the endpoint does not follow the `/addresses` layout
+fn smart_contract_url(addresses_base_url: &str, address: &str) -> Option<String> {
+    let api_base = addresses_base_url
+        .trim_end_matches('/')
+        .strip_suffix("/addresses")?;
+    Some(format!("{}/smart-contracts/{}", api_base, address))
+}
+
+/// Fetches a verified contract's ABI from Blockscout and stores it in `contract_abis`
+///
+/// Retried per `config.retry_config()` and paced by the chain's Blockscout
+/// budget like the address lookups. Best effort: the caller only logs failures.
+///
+/// # Returns
+/// * `Ok(true)` - ABI stored (inserted or refreshed)
+/// * `Ok(false)` - Endpoint layout unknown, contract unknown or response had no ABI
+/// * `Err` - HTTP (after retries), parse or database error
+async fn store_contract_abi(config: &Config, base_url: &str, address: &str, chainid: i64) -> Result<bool> {
+    let Some(url) = smart_contract_url(base_url, address) else {
+        return Ok(false);
+    };
+
+    let resp = match get_json_with_retry::<SmartContractResponse>(
+        config,
+        &url,
+        |req| req.header("accept", "application/json"),
+        config.retry_config(),
+        None,
+        config.blockscout_rate_limiter(chainid),
+    )
+    .await
+    {
+        FetchResult::Success(resp) => resp,
+        FetchResult::Empty | FetchResult::NotFound => return Ok(false),
+        FetchResult::Failed(e) => return Err(anyhow!(e)),
+    };
+
+    let Some(abi) = resp.abi.filter(|abi| abi.is_array()) else {
+        return Ok(false);
+    };
+
+    sqlx::query(
+        r#"
+        INSERT INTO contract_abis (address, chainid, abi)
+        VALUES ($1, $2, $3)
+        ON CONFLICT (LOWER(address), chainid)
+        DO UPDATE SET abi = EXCLUDED.abi, fetched_at = NOW()
+        "#,
+    )
+    .bind(address.to_lowercase())
+    .bind(chainid)
+    .bind(sqlx::types::Json(&abi))
+    .execute(&config.postgres_db.pool)
+    .await?;
+
+    Ok(true)
 }
 
 /// Token information from Blockscout API
@@ -944,6 +2011,137 @@ struct TokenInfo {
     /// Token standard type (e.g., "ERC-20", "ERC-721")
     #[serde(rename = "type")]
     token_type: Option<String>,
+    /// Number of holders (Blockscout v2 reports it as a decimal string)
+    #[serde(default, deserialize_with = "lenient_count")]
+    holders_count: Option<u64>,
+}
+
+/// Deserializes a count given as a JSON number or a decimal string
+///
+/// Anything else (null, negative, malformed) becomes `None` instead of
+/// failing the whole response.
+fn lenient_count<'de, D: serde::Deserializer<'de>>(deserializer: D) -> Result<Option<u64>, D::Error> {
+    Ok(match Value::deserialize(deserializer)? {
+        Value::Number(n) => n.as_u64(),
+        Value::String(s) => s.trim().parse().ok(),
+        _ => None,
+    })
+}
+
+/// Blockscout response body: a single address, or one page of a list endpoint
+///
+/// `Paged` must come first: every `BlockscoutResponse` field has a default,
+/// so `Single` would also match a paginated body.
+#[derive(Debug, Deserialize)]
+#[serde(untagged)]
+enum BlockscoutBody {
+    Paged {
+        items: Vec<BlockscoutResponse>,
+        #[serde(default)]
+        next_page_params: Option<Value>,
+    },
+    Single(BlockscoutResponse),
+}
+
+/// Appends Blockscout `next_page_params` to `url` as query parameters
+///
+/// Null values are dropped; strings are used as-is and other values in
+/// their JSON form.
+fn blockscout_page_url(url: &str, page_params: Option<&Value>) -> Result<String> {
+    let Some(params) = page_params.and_then(Value::as_object) else {
+        return Ok(url.to_string());
+    };
+    let mut url = url::Url::parse(url).with_context(|| format!("Invalid Blockscout URL: {}", url))?;
+    {
+        let mut query = url.query_pairs_mut();
+        for (key, value) in params {
+            match value {
+                Value::Null => {}
+                Value::String(s) => {
+                    query.append_pair(key, s);
+                }
+                other => {
+                    query.append_pair(key, &other.to_string());
+                }
+            }
+        }
+    }
+    Ok(url.into())
+}
+
+/// Fetches one page of a Blockscout endpoint
+///
+/// Single-address responses come back as one item with no next page.
+///
+/// Every attempt is paced by the chain's Blockscout rate limiter.
+///
+/// # Arguments
+/// * `url` - Endpoint URL without page parameters
+/// * `chainid` - Chain whose Blockscout budget the request counts against
+/// * `page_params` - `next_page_params` of the previous page (`None` for the first)
+///
+/// # Returns
+/// * `Ok((items, next_page_params))` - `next_page_params` is `None` on the last page;
+///   `items` is empty for a 404 or an empty body
+/// * `Err` - Request failed after retries
+async fn fetch_blockscout_page(
+    config: &Config,
+    url: &str,
+    chainid: i64,
+    page_params: Option<Value>,
+) -> Result<(Vec<BlockscoutResponse>, Option<Value>)> {
+    let page_url = blockscout_page_url(url, page_params.as_ref())?;
+    match get_json_with_retry::<BlockscoutBody>(
+        config,
+        &page_url,
+        |req| req.header("accept", "application/json"),
+        config.retry_config(),
+        None,
+        config.blockscout_rate_limiter(chainid),
+    )
+    .await
+    {
+        FetchResult::Success(BlockscoutBody::Paged { items, next_page_params }) => {
+            Ok((items, next_page_params.filter(|p| !p.is_null())))
+        }
+        FetchResult::Success(BlockscoutBody::Single(item)) => Ok((vec![item], None)),
+        FetchResult::Empty | FetchResult::NotFound => Ok((Vec::new(), None)),
+        FetchResult::Failed(e) => Err(anyhow!(e)),
+    }
+}
+
+/// Days after which the Blockscout sync re-checks an ERC-20's holder count
+const HOLDER_COUNT_REFRESH_DAYS: i32 = 7;
+
+/// Days between retries of a proxy implementation lookup that found nothing
+const IMPLEMENTATION_RETRY_DAYS: i32 = 7;
+
+/// Upper bound of pages followed per Blockscout endpoint
+const MAX_BLOCKSCOUT_PAGES: usize = 50;
+
+/// Fetches every page of a Blockscout endpoint by following `next_page_params`
+///
+/// Stops early (keeping the items fetched so far) after
+/// [`MAX_BLOCKSCOUT_PAGES`] pages or when a cursor repeats, so a
+/// misbehaving endpoint can't keep the sync paging forever.
+async fn fetch_blockscout_pages(config: &Config, url: &str, chainid: i64) -> Result<Vec<BlockscoutResponse>> {
+    let mut items = Vec::new();
+    let mut page_params = None;
+    let mut seen_cursors = HashSet::new();
+    for _ in 0..MAX_BLOCKSCOUT_PAGES {
+        let (page, next) = fetch_blockscout_page(config, url, chainid, page_params).await?;
+        items.extend(page);
+        let Some(next) = next else {
+            return Ok(items);
+        };
+        if !seen_cursors.insert(next.to_string()) {
+            warn!("⚠️ [chainid={}] Blockscout repeated page cursor {} for {}, stopping", chainid, next, url);
+            return Ok(items);
+        }
+        page_params = Some(next);
+    }
+    warn!("⚠️ [chainid={}] Stopped paging {} after {} pages", chainid, url, MAX_BLOCKSCOUT_PAGES);
+    Ok(items)
 }
 
 /// Updates metadata with contract verification and risk information from Blockscout
@@ -955,49 +2153,71 @@ struct TokenInfo {
 /// # Workflow
 /// 1. Load all metadata records that are missing token_type, is_verified, or risk_level
 /// 2. For each record, query the corresponding Blockscout API endpoint
-/// 3. Parse response and extract: token_type, is_verified, is_scam flags
-/// 4. Update database with new information (only non-null fields)
-/// 5. Report statistics by chain
+/// 3. Parse response (following `next_page_params` if paginated) and extract: token_type, is_verified, is_scam flags,
+///    proxy implementation, holder count
+/// 4. Compute risk_score/risk_level (see [`compute_risk`]) and update the database
+/// 5. For verified contracts, store the ABI in `contract_abis`
+/// 6. Report statistics by chain
 ///
 /// # Optimization Strategies
-/// - Skip records that already have all three fields populated
+/// - Skip records that already have all three fields populated (and, for ERC-20, a holder count
+///   checked within [`HOLDER_COUNT_REFRESH_DAYS`]; for verified contracts, a stored ABI)
 /// - Skip chains without configured Blockscout endpoints
 /// - Skip non-contract addresses (is_contract = false)
-/// - Use COALESCE in UPDATE to preserve existing non-null values
-/// - Retry failed requests up to 3 times with 500ms delay
+/// - Use COALESCE in UPDATE to preserve existing non-null values (except the proxy columns,
+///   which always mirror the latest response)
+/// - Retry failed requests per `config.retry_config()`
 ///
 /// # Arguments
 /// * `config` - Application configuration with Blockscout endpoints and HTTP client
 ///
 /// # Returns
-/// * `Ok(())` - Update completed (some failures are tolerated)
+/// * `Ok(SyncStats)` - Update completed (some failures are tolerated)
 /// * `Err` - Fatal error (database connection failure)
 ///
 /// # Performance
-/// - Rate limit: 200ms between requests
+/// - Rate limit: per chain via `BLOCKSCOUT_RATE_LIMIT_<chainid>` (300/min if unset), counting
+///   every page, retry, implementation lookup and ABI fetch
 /// - Progress log: every 20 records
-/// - Typical runtime: ~5-10 minutes for 1000 contracts
+/// - Typical runtime: depends on the configured per-chain budgets
 ///
 /// # Error Handling
 /// - Individual API failures are logged but don't stop execution
 /// - Final summary shows failure counts per chain
 /// - Non-contract addresses are silently skipped
-pub async fn update_metadata_from_blockscout(config: &Config) -> Result<()> {
+#[instrument(skip_all)]
+pub async fn update_metadata_from_blockscout(config: &Config) -> Result<SyncStats> {
     let pool = &config.postgres_db.pool;
-    let client = &config.http_client;
 
     // Step 1: Load all metadata records (only fetch fields we need to check)
     // This minimizes memory usage when dealing with large datasets
     let rows: Vec<MetadataPartial> = sqlx::query_as::<_, MetadataPartial>(
-        r#"SELECT id, chainid, address, token_type, is_verified, risk_level FROM metadata"#,
+        r#"
+        SELECT
+            m.id, m.chainid, m.address, m.token_type, m.is_verified, m.risk_level,
+            (m.holders_checked_at IS NULL OR m.holders_checked_at < NOW() - make_interval(days => $1)) AS holders_due,
+            m.is_proxy, m.implementation_verified,
+            (m.implementation_checked_at IS NULL OR m.implementation_checked_at < NOW() - make_interval(days => $2))
+                AS implementation_due,
+            (m.homepage IS NOT NULL OR m.image IS NOT NULL OR m.description IS NOT NULL) AS has_metadata,
+            ca.address IS NULL AS missing_abi,
+            md.market_cap
+        FROM metadata m
+        LEFT JOIN LATERAL (
+            SELECT market_cap FROM marketdata WHERE token_id = m.tokenid LIMIT 1
+        ) md ON TRUE
+        LEFT JOIN contract_abis ca ON LOWER(ca.address) = LOWER(m.address) AND ca.chainid = m.chainid
+        "#,
     )
+    .bind(HOLDER_COUNT_REFRESH_DAYS)
+    .bind(IMPLEMENTATION_RETRY_DAYS)
     .fetch_all(pool)
     .await
     .context("Failed to fetch metadata rows")?;
 
     if rows.is_empty() {
         info!("⚠️ No metadata rows found, skipping Blockscout update");
-        return Ok(());
+        return Ok(SyncStats::default());
     }
 
     let mut updated_count = 0usize;
@@ -1007,7 +2227,26 @@ pub async fn update_metadata_from_blockscout(config: &Config) -> Result<()> {
     for (i, row) in rows.iter().enumerate() {
         // Step 2: Skip if all required fields already populated (optimization)
         // No need to call API if we already have complete data
-        if row.token_type.is_some() && row.is_verified.is_some() && row.risk_level.is_some() {
+        // ERC-20 rows get another pass once their holder count is due for a refresh,
+        // as do proxies whose implementation lookup is due (failed lookups are
+        // retried weekly, not every run) and verified
+        // contracts whose ABI is missing (stored before ABIs were, or the fetch failed)
+        let needs_holders = row.token_type.as_deref() == Some("ERC-20") && row.holders_due;
+        let needs_implementation =
+            row.is_proxy == Some(true) && row.implementation_verified.is_none() && row.implementation_due;
+        let needs_abi = row.is_verified == Some(true)
+            && row.missing_abi
+            && config
+                .blockscout_endpoints
+                .get(&row.chainid)
+                .is_some_and(|base_url| smart_contract_url(base_url, &row.address).is_some());
+        if row.token_type.is_some()
+            && row.is_verified.is_some()
+            && row.risk_level.is_some()
+            && !needs_holders
+            && !needs_implementation
+            && !needs_abi
+        {
             skipped_count += 1;
             continue;
         }
@@ -1024,80 +2263,57 @@ pub async fn update_metadata_from_blockscout(config: &Config) -> Result<()> {
 
         let api_url = format!("{}/{}", base_url.trim_end_matches('/'), row.address);
 
-        // Step 4: Call Blockscout API with retry mechanism (up to 3 attempts)
-        // Retries handle transient network issues and rate limiting
-        let mut resp_opt = None;
-        for attempt in 1..=3 {
-            match client
-                .get(&api_url)
-                .header("accept", "application/json")
-                .send()
-                .await
-            {
-                Ok(r) if r.status().is_success() => {
-                    resp_opt = Some(r);
-                    break; // Success, exit retry loop
-                }
-                Ok(r) => {
-                    warn!(
-                        "⚠️ [chainid={}] attempt {}/3: HTTP {} for {}",
-                        row.chainid,
-                        attempt,
-                        r.status(),
-                        row.address
-                    );
-                }
-                Err(e) => {
-                    warn!(
-                        "⚠️ [chainid={}] attempt {}/3 failed for {}: {:?}",
-                        row.chainid, attempt, row.address, e
-                    );
-                }
-            }
-            // Wait before retry (exponential backoff could be added here)
-            sleep(Duration::from_millis(500)).await;
-        }
-
-        // All retry attempts failed, record and continue to next address
-        let Some(resp) = resp_opt else {
-            *fail_count_by_chain.entry(row.chainid).or_default() += 1;
-            continue;
-        };
-
-        // Step 5: Parse JSON response from Blockscout API
-        let data: BlockscoutResponse = match resp.json().await {
-            Ok(json) => json,
+        // Step 4: Call Blockscout API (retried per `config.retry_config()`),
+        // every attempt paced by the chain's BLOCKSCOUT_RATE_LIMIT_<chainid> budget
+        let data = match fetch_blockscout_pages(config, &api_url, row.chainid).await {
+            Ok(items) => match items.into_iter().next() {
+                Some(data) => data,
+                None => continue, // Unknown address
+            },
             Err(e) => {
                 warn!(
-                    "⚠️ Failed to parse Blockscout JSON for {}: {:?}",
-                    row.address, e
+                    "⚠️ [chainid={}] Blockscout request failed for {}: {:#}",
+                    row.chainid, row.address, e
                 );
                 *fail_count_by_chain.entry(row.chainid).or_default() += 1;
                 continue;
             }
         };
 
-        // Step 6: Skip non-contract addresses (EOAs don't have metadata)
+        // Step 5: Skip non-contract addresses (EOAs don't have metadata)
         if !data.is_contract {
             continue;
         }
 
-        // Step 7: Extract relevant fields from API response
-        // risk_level: Only set if flagged as scam (None means safe/unknown)
-        let risk_level = if data.is_scam {
-            Some("scam".to_string())
-        } else {
-            None
-        };
+        // Step 6: Extract relevant fields from API response and score the token
         let token_type = data.token.as_ref().and_then(|t| t.token_type.clone());
+        let holder_count = data
+            .token
+            .as_ref()
+            .and_then(|t| t.holders_count)
+            .and_then(|n| i64::try_from(n).ok());
         let is_verified = Some(data.is_verified);
-
-        // Step 8: Check if we have any new data to update
+        let proxy_implementation = data.proxy_implementation();
+        let is_proxy = proxy_implementation.is_some();
+        let implementation_verified = match &proxy_implementation {
+            Some(implementation) => implementation_verified(config, base_url, row.chainid, implementation).await,
+            None => None,
+        };
+        let (risk_score, risk_level) = compute_risk(RiskInputs {
+            is_verified,
+            is_scam: data.is_scam,
+            has_metadata: row.has_metadata,
+            market_cap: row.market_cap,
+        });
+
+        // Step 7: Check if we have any new data to update
         // Note: is_verified is always Some, so we always have at least one field to update
         // This is intentional - we want to record verification status even if false
 
-        // Step 9: Update database with new information
-        // COALESCE ensures we don't overwrite existing data with NULL
+        // Step 8: Update database with new information
+        // COALESCE ensures we don't overwrite existing data with NULL; the proxy
+        // columns are replaced together (a contract can stop being a proxy), and
+        // a changed implementation drops the verification of the old one
         let res = sqlx::query(
             r#"
             UPDATE metadata
@@ -1105,13 +2321,31 @@ pub async fn update_metadata_from_blockscout(config: &Config) -> Result<()> {
                 token_type = COALESCE($1, token_type),
                 is_verified = COALESCE($2, is_verified),
                 risk_level = COALESCE($3, risk_level),
+                risk_score = $4,
+                proxy_implementation = $5,
+                is_proxy = $6,
+                holder_count = COALESCE($7, holder_count),
+                holders_checked_at = NOW(),
+                implementation_verified = CASE
+                    WHEN $5::TEXT IS DISTINCT FROM proxy_implementation THEN $8
+                    ELSE COALESCE($8, implementation_verified)
+                END,
+                implementation_checked_at = CASE
+                    WHEN $5::TEXT IS NOT NULL THEN NOW()
+                    ELSE implementation_checked_at
+                END,
                 updated_at = NOW()
-            WHERE id = $4
+            WHERE id = $9
             "#,
         )
         .bind(&token_type)
-        .bind(&is_verified)
+        .bind(is_verified)
         .bind(&risk_level)
+        .bind(risk_score)
+        .bind(&proxy_implementation)
+        .bind(is_proxy)
+        .bind(holder_count)
+        .bind(implementation_verified)
         .bind(row.id)
         .execute(pool)
         .await;
@@ -1123,6 +2357,13 @@ pub async fn update_metadata_from_blockscout(config: &Config) -> Result<()> {
                     "✅ Updated metadata id={} (chainid={}, address={})",
                     row.id, row.chainid, row.address
                 );
+
+                // Step 9: Keep the ABI of verified contracts (best effort)
+                if data.is_verified
+                    && let Err(e) = store_contract_abi(config, base_url, &row.address, row.chainid).await
+                {
+                    warn!("⚠️ Failed to store ABI for {}: {:?}", row.address, e);
+                }
             }
             Err(e) => {
                 warn!(
@@ -1144,8 +2385,6 @@ pub async fn update_metadata_from_blockscout(config: &Config) -> Result<()> {
             );
         }
 
-        // Rate limiting: 200ms delay between requests to respect API limits
-        sleep(Duration::from_millis(200)).await;
     }
 
     // Step 10: Final summary with statistics
@@ -1159,5 +2398,420 @@ pub async fn update_metadata_from_blockscout(config: &Config) -> Result<()> {
         warn!("⚠️ Chain {}: {} failures", chainid, fails);
     }
 
-    Ok(())
+    Ok(SyncStats {
+        updated: updated_count,
+        ..Default::default()
+    })
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    /// Test that an NFT list page is fetched through the CoinGecko limiter
+    #[tokio::test]
+    async fn test_fetch_nft_list_page() {
+        let mut config = Config::for_tests();
+        config.coingecko_rate_limiter.reset();
+        let nfts = serde_json::json!([
+            {"id": "pudgy-penguins", "contract_address": "0xBd3531dA5CF5857e7CfAA92426877b022e612cf8", "asset_platform_id": "ethereum"},
+            {"id": "azuki", "contract_address": "0xED5AF388653567Af2F388E6224dC7C4b3241C544", "asset_platform_id": "ethereum"}
+        ]);
+        config.coingecko_base_url = crate::utils::serve_json("/api/v3/nfts/list", nfts).await;
+
+        let page = fetch_nft_list_page(&config, 1).await.unwrap();
+        assert_eq!(page.len(), 2);
+        assert_eq!(page[0]["id"], "pudgy-penguins");
+        assert_eq!(config.coingecko_rate_limiter.stats().total_acquired, 1);
+    }
+
+    /// Test that a coin CoinGecko no longer knows comes back as NotFound
+    ///
+    /// This is what the refresh sees for a token that already has metadata
+    /// and was since dropped, and what makes it deactivate the rows.
+    #[tokio::test]
+    async fn test_fetch_coin_detail_not_found() {
+        let mut config = Config::for_tests();
+        config.coingecko_rate_limiter.reset();
+        let coin = serde_json::json!({"id": "usd-coin", "symbol": "usdc", "name": "USDC"});
+        config.coingecko_base_url = crate::utils::serve_json("/api/v3/coins/usd-coin", coin).await;
+
+        let found = fetch_coin_detail(&config, "usd-coin").await;
+        assert!(matches!(found, FetchResult::Success(ref c) if c.symbol_and_name() == Some(("usdc", "USDC"))));
+
+        let gone = fetch_coin_detail(&config, "delisted-coin").await;
+        assert!(matches!(gone, FetchResult::NotFound));
+        assert_eq!(config.coingecko_rate_limiter.stats().total_acquired, 2);
+    }
+
+    #[test]
+    fn test_coin_list_entry_deserialization() {
+        let json = r#"[
+            {"id": "usd-coin", "symbol": "usdc", "name": "USDC",
+             "platforms": {"ethereum": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "solana": null}},
+            {"id": "bitcoin", "symbol": "btc", "name": "Bitcoin", "platforms": {}},
+            {"symbol": "bad"}
+        ]"#;
+
+        let entries: Vec<CoinListEntry> = serde_json::from_str(json).unwrap();
+        assert_eq!(entries.len(), 3);
+
+        let platforms = entries[0].platforms.as_ref().unwrap();
+        assert_eq!(
+            platforms.get("ethereum").unwrap().as_deref(),
+            Some("0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48")
+        );
+        assert_eq!(platforms.get("solana").unwrap().as_deref(), None);
+        assert!(entries[1].platforms.as_ref().unwrap().is_empty());
+        assert!(entries[2].id.is_none());
+        assert!(entries[2].platforms.is_none());
+    }
+
+    #[test]
+    fn test_relevant_coin_list_seed() {
+        let json = r#"[
+            {"id": "usd-coin", "symbol": "usdc", "name": "USDC",
+             "platforms": {"ethereum": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48", "solana": "EPjF", "base": ""}},
+            {"id": "bitcoin", "symbol": "btc", "name": "Bitcoin", "platforms": {}},
+            {"id": "bonk", "symbol": "bonk", "name": "Bonk", "platforms": {"solana": "DezX"}},
+            {"symbol": "bad", "platforms": {"ethereum": "0x1"}}
+        ]"#;
+
+        let is_relevant = |platform: &str| platform == "ethereum" || platform == "base";
+        let list = RelevantCoinListSeed { is_relevant: &is_relevant }.parse(json).unwrap();
+        assert_eq!(list.total, 4);
+        assert_eq!(list.entries.len(), 1);
+        assert_eq!(list.entries[0].id.as_deref(), Some("usd-coin"));
+        let platforms = list.entries[0].platforms.as_ref().unwrap();
+        assert_eq!(platforms.len(), 1);
+        assert!(platforms.contains_key("ethereum"));
+
+        assert!(RelevantCoinListSeed { is_relevant: &is_relevant }.parse("[] x").is_err());
+    }
+
+    #[test]
+    fn test_nft_market_data_from_detail() {
+        let resp: Value = serde_json::from_str(
+            r#"{
+                "id": "pudgy-penguins",
+                "floor_price": {"native_currency": 10.5, "usd": 38250.2},
+                "market_cap": {"native_currency": 92000, "usd": 335000000},
+                "volume_24h": {"native_currency": 210.1}
+            }"#,
+        )
+        .unwrap();
+
+        assert_eq!(
+            NftMarketData::from_detail(&resp),
+            NftMarketData {
+                floor_price_usd: Some(38250.2),
+                volume_24h_usd: None,
+                market_cap_usd: Some(335000000.0),
+            }
+        );
+    }
+
+    #[test]
+    fn test_coin_detail_deserialization() {
+        let json = r#"{
+            "id": "usd-coin",
+            "symbol": "usdc",
+            "name": "USDC",
+            "links": {"homepage": ["", "https://www.circle.com/en/usdc"]},
+            "image": {"large": "https://example.com/usdc.png"},
+            "description": {"en": "USDC is a stablecoin"},
+            "additional_notices": ["notice"],
+            "market_cap_rank": 7,
+            "developer_data": {"forks": 120, "stars": 450, "commit_count_4_weeks": 3}
+        }"#;
+
+        let detail: CoinDetail = serde_json::from_str(json).unwrap();
+        assert_eq!(detail.id, "usd-coin");
+        assert_eq!(detail.homepage(), Some("https://www.circle.com/en/usdc"));
+        assert_eq!(detail.image(), Some("https://example.com/usdc.png"));
+        assert_eq!(detail.description(), Some("USDC is a stablecoin"));
+        assert!(detail.additional_notices.is_some());
+        assert_eq!(detail.developer_data.unwrap()["stars"], 450);
+    }
+
+    #[test]
+    fn test_coin_detail_platforms() {
+        let json = r#"{
+            "id": "usd-coin",
+            "symbol": "usdc",
+            "name": "USDC",
+            "platforms": {
+                "ethereum": "0xA0b86991c6218b36c1d19D4a2e9Eb0cE3606eB48",
+                "solana": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v",
+                "polygon-pos": null
+            }
+        }"#;
+        let detail: CoinDetail = serde_json::from_str(json).unwrap();
+        assert_eq!(
+            detail.platforms(),
+            Some(serde_json::json!({
+                "ethereum": "0xa0b86991c6218b36c1d19d4a2e9eb0ce3606eb48",
+                "solana": "EPjFWdd5AufqSSqeM2qN1xzybapC8G4wEGGkZwyTDt1v"
+            }))
+        );
+
+        let native = r#"{"id": "bitcoin", "symbol": "btc", "name": "Bitcoin", "platforms": {"": ""}}"#;
+        assert!(serde_json::from_str::<CoinDetail>(native).unwrap().platforms().is_none());
+    }
+
+    #[test]
+    fn test_coin_detail_developer_data() {
+        let detail = |developer_data: Value| -> CoinDetail {
+            serde_json::from_value(serde_json::json!({
+                "id": "usd-coin", "symbol": "usdc", "name": "USDC", "developer_data": developer_data
+            }))
+            .unwrap()
+        };
+        assert_eq!(
+            detail(serde_json::json!({"forks": 120, "stars": 450})).developer_data(),
+            Some(serde_json::json!({"forks": 120, "stars": 450}))
+        );
+        for stars in [serde_json::json!(4.5), serde_json::json!("450"), serde_json::json!(-1), serde_json::json!(3_000_000_000u64)] {
+            assert_eq!(
+                detail(serde_json::json!({"forks": 120, "stars": stars})).developer_data(),
+                Some(serde_json::json!({"forks": 120}))
+            );
+        }
+        assert_eq!(detail(serde_json::json!({"stars": null})).developer_data(), None);
+    }
+
+    #[test]
+    fn test_coin_detail_community_data() {
+        let detail: CoinDetail = serde_json::from_value(serde_json::json!({
+            "id": "usd-coin", "symbol": "usdc", "name": "USDC",
+            "community_data": {
+                "twitter_followers": 4030958,
+                "reddit_subscribers": -1,
+                "reddit_average_posts_48h": 0.5,
+                "telegram_channel_user_count": null,
+                "facebook_likes": "n/a"
+            }
+        }))
+        .unwrap();
+        assert_eq!(
+            detail.community_data(),
+            Some(serde_json::json!({"twitter_followers": 4030958, "reddit_average_posts_48h": 0.5}))
+        );
+
+        let empty: CoinDetail = serde_json::from_value(serde_json::json!({
+            "id": "x", "symbol": "x", "name": "x", "community_data": {"twitter_followers": null}
+        }))
+        .unwrap();
+        assert_eq!(empty.community_data(), None);
+    }
+
+    #[test]
+    fn test_coin_detail_genesis_date() {
+        let detail = |genesis: Value| -> CoinDetail {
+            serde_json::from_value(serde_json::json!({
+                "id": "ethereum", "symbol": "eth", "name": "Ethereum", "genesis_date": genesis
+            }))
+            .unwrap()
+        };
+        assert_eq!(detail(Value::from("2015-07-30")).genesis_date(), NaiveDate::from_ymd_opt(2015, 7, 30));
+        assert!(detail(Value::Null).genesis_date().is_none());
+        assert!(detail(Value::from("July 2015")).genesis_date().is_none());
+    }
+
+    #[test]
+    fn test_coin_detail_tickers() {
+        let ticker = |exchange: &str, volume: f64| {
+            serde_json::json!({
+                "base": "USDC", "target": "USDT", "last": 1.0001, "volume": volume,
+                "exchange": {"name": exchange, "identifier": exchange.to_lowercase()}
+            })
+        };
+        let mut tickers: Vec<Value> = (0..12).map(|i| ticker(&format!("Ex{}", i), i as f64)).collect();
+        tickers.push(serde_json::json!({"base": "USDC", "volume": 1e9}));
+        let detail: CoinDetail = serde_json::from_value(serde_json::json!({
+            "id": "usd-coin", "symbol": "usdc", "name": "USDC", "tickers": tickers
+        }))
+        .unwrap();
+
+        let stored = detail.tickers().unwrap();
+        let stored = stored.as_array().unwrap();
+        assert_eq!(stored.len(), MAX_STORED_TICKERS);
+        assert_eq!(stored[0]["exchange"], "Ex11");
+        assert_eq!(stored[9]["exchange"], "Ex2");
+        assert_eq!(
+            stored[0],
+            serde_json::json!({"exchange": "Ex11", "base": "USDC", "target": "USDT", "last": 1.0001, "volume": 11.0})
+        );
+
+        // Delisted everywhere: stored as empty so the refresh overwrites old listings
+        let unlisted: CoinDetail =
+            serde_json::from_str(r#"{"id": "foo", "symbol": "foo", "name": "Foo", "tickers": []}"#).unwrap();
+        assert_eq!(unlisted.tickers(), Some(serde_json::json!([])));
+        let not_requested: CoinDetail = serde_json::from_str(r#"{"id": "foo", "symbol": "foo", "name": "Foo"}"#).unwrap();
+        assert_eq!(not_requested.tickers(), None);
+    }
+
+    #[test]
+    fn test_coin_detail_missing_optional_fields() {
+        let json = r#"{"id": "foo", "symbol": "foo", "name": "Foo", "links": {"homepage": null}}"#;
+
+        let detail: CoinDetail = serde_json::from_str(json).unwrap();
+        assert_eq!(detail.homepage(), None);
+        assert_eq!(detail.image(), None);
+        assert_eq!(detail.description(), None);
+        assert!(detail.additional_notices.is_none());
+        assert!(detail.developer_data.is_none());
+        assert!(detail.social_links().is_none());
+    }
+
+    #[test]
+    fn test_coin_detail_null_symbol() {
+        let detail: CoinDetail = serde_json::from_str(r#"{"id": "foo", "symbol": null, "name": "Foo"}"#).unwrap();
+        assert_eq!(detail.symbol_and_name(), None);
+
+        let detail: CoinDetail = serde_json::from_str(r#"{"id": "foo", "symbol": "foo", "name": ""}"#).unwrap();
+        assert_eq!(detail.symbol_and_name(), None);
+
+        let detail: CoinDetail = serde_json::from_str(r#"{"id": "foo"}"#).unwrap();
+        assert_eq!(detail.symbol_and_name(), None);
+
+        let detail: CoinDetail = serde_json::from_str(r#"{"id": "foo", "symbol": "foo", "name": "Foo"}"#).unwrap();
+        assert_eq!(detail.symbol_and_name(), Some(("foo", "Foo")));
+    }
+
+    #[test]
+    fn test_smart_contract_url() {
+        assert_eq!(
+            smart_contract_url("https://eth.blockscout.com/api/v2/addresses", "0xabc").as_deref(),
+            Some("https://eth.blockscout.com/api/v2/smart-contracts/0xabc")
+        );
+        assert_eq!(
+            smart_contract_url("https://base.blockscout.com/api/v2/addresses/", "0xabc").as_deref(),
+            Some("https://base.blockscout.com/api/v2/smart-contracts/0xabc")
+        );
+        assert!(smart_contract_url("https://example.com/custom", "0xabc").is_none());
+    }
+
+    /// Test that the ABI fetch goes through the chain's Blockscout limiter
+    #[tokio::test]
+    async fn test_store_contract_abi_unknown_contract() {
+        let mut config = Config::for_tests();
+        config.add_blockscout_endpoint(1, "http://127.0.0.1/api/v2/addresses".to_string());
+        let server = crate::utils::serve_json("/api/v2/smart-contracts/0xknown", serde_json::json!({})).await;
+        let base_url = format!("{}/api/v2/addresses", server);
+
+        // 404 for an unknown contract: nothing to store, no database access
+        assert!(!store_contract_abi(&config, &base_url, "0xabc", 1).await.unwrap());
+        assert_eq!(config.blockscout_rate_limiters[&1].stats().total_acquired, 1);
+    }
+
+    #[test]
+    fn test_coin_detail_social_links() {
+        let json = r#"{
+            "id": "uniswap",
+            "symbol": "uni",
+            "name": "Uniswap",
+            "links": {
+                "homepage": ["https://uniswap.org/"],
+                "twitter_screen_name": "Uniswap",
+                "telegram_channel_identifier": "",
+                "chat_url": ["https://discord.com/invite/uniswap", ""],
+                "announcement_url": ["", ""],
+                "repos_url": {"github": ["https://github.com/Uniswap/v3-core"], "bitbucket": []}
+            }
+        }"#;
+
+        let detail: CoinDetail = serde_json::from_str(json).unwrap();
+        let links = detail.social_links().unwrap();
+        assert_eq!(links["twitter"], "Uniswap");
+        assert!(links["telegram"].is_null());
+        assert_eq!(links["github"][0], "https://github.com/Uniswap/v3-core");
+        assert_eq!(links["chat"].as_array().unwrap().len(), 1);
+        assert!(links["announcement"].as_array().unwrap().is_empty());
+    }
+
+    #[test]
+    fn test_blockscout_proxy_implementation() {
+        let legacy: BlockscoutResponse = serde_json::from_str(
+            r#"{"is_contract": true, "implementation_address": "0xABCDEF0000000000000000000000000000000001"}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            legacy.proxy_implementation().as_deref(),
+            Some("0xabcdef0000000000000000000000000000000001")
+        );
+
+        let listed: BlockscoutResponse = serde_json::from_str(
+            r#"{"is_contract": true, "implementations": [{"address": "0x43506849D7C04F9138D1A2050bbF3A0c054402dd", "name": "FiatTokenV2_2"}]}"#,
+        )
+        .unwrap();
+        assert_eq!(
+            listed.proxy_implementation().as_deref(),
+            Some("0x43506849d7c04f9138d1a2050bbf3a0c054402dd")
+        );
+
+        let plain: BlockscoutResponse =
+            serde_json::from_str(r#"{"is_contract": true, "implementations": []}"#).unwrap();
+        assert!(plain.proxy_implementation().is_none());
+    }
+
+    #[test]
+    fn test_blockscout_holders_count() {
+        let holders = |token: Value| {
+            let json = serde_json::json!({"is_contract": true, "token": token});
+            serde_json::from_value::<BlockscoutResponse>(json).unwrap().token.unwrap().holders_count
+        };
+        assert_eq!(holders(serde_json::json!({"type": "ERC-20", "holders_count": "3456789"})), Some(3_456_789));
+        assert_eq!(holders(serde_json::json!({"type": "ERC-20", "holders_count": 42})), Some(42));
+        assert_eq!(holders(serde_json::json!({"type": "ERC-20", "holders_count": null})), None);
+        assert_eq!(holders(serde_json::json!({"type": "ERC-20", "holders_count": "n/a"})), None);
+        assert_eq!(holders(serde_json::json!({"type": "ERC-721"})), None);
+    }
+
+    #[test]
+    fn test_blockscout_body_shapes() {
+        let paged: BlockscoutBody = serde_json::from_str(
+            r#"{"items": [{"is_contract": true}, {"is_contract": false}],
+                "next_page_params": {"block_number": 123, "index": 5, "hash": null}}"#,
+        )
+        .unwrap();
+        let BlockscoutBody::Paged { items, next_page_params } = paged else {
+            panic!("expected a page");
+        };
+        assert_eq!(items.len(), 2);
+        assert!(next_page_params.is_some());
+
+        let single: BlockscoutBody = serde_json::from_str(r#"{"is_contract": true, "is_verified": true}"#).unwrap();
+        assert!(matches!(single, BlockscoutBody::Single(ref r) if r.is_verified));
+    }
+
+    /// Test that paging stops when Blockscout keeps returning the same cursor
+    #[tokio::test]
+    async fn test_fetch_blockscout_pages_repeated_cursor() {
+        let mut config = Config::for_tests();
+        let body = serde_json::json!({
+            "items": [{"is_contract": true}],
+            "next_page_params": {"page": 2}
+        });
+        // The query string is ignored, so every page returns the same cursor
+        let server = crate::utils::serve_json("/api/v2/addresses/0xabc", body).await;
+        config.add_blockscout_endpoint(1, format!("{}/api/v2/addresses", server));
+
+        let url = format!("{}/api/v2/addresses/0xabc", server);
+        let items = fetch_blockscout_pages(&config, &url, 1).await.unwrap();
+        assert_eq!(items.len(), 2);
+        assert_eq!(config.blockscout_rate_limiters[&1].stats().total_acquired, 2);
+    }
+
+    #[test]
+    fn test_blockscout_page_url() {
+        let base = "https://eth.blockscout.com/api/v2/tokens";
+        assert_eq!(blockscout_page_url(base, None).unwrap(), base);
+
+        let params = serde_json::json!({"block_number": 123, "hash": null, "type": "ERC-20"});
+        assert_eq!(
+            blockscout_page_url(base, Some(&params)).unwrap(),
+            "https://eth.blockscout.com/api/v2/tokens?block_number=123&type=ERC-20"
+        );
+    }
 }
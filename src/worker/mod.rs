@@ -1,3 +1,39 @@
 pub mod metadata;
 pub mod marketdata;
 pub mod forex;
+pub mod risk;
+pub mod dex;
+
+use serde::Serialize;
+
+/// Counters reported by a sync worker run
+///
+/// Returned by every sync entry point so that schedulers and the manager
+/// RPC can report what a run actually did.
+#[derive(Debug, Default, Clone, Copy, Serialize)]
+pub struct SyncStats {
+    /// Rows inserted
+    pub inserted: usize,
+    /// Existing rows updated
+    pub updated: usize,
+    /// API pages fetched
+    pub pages: usize,
+    /// Upstream items received across all pages
+    pub fetched: usize,
+    /// Rows deactivated because the upstream source no longer lists them
+    pub deactivated: usize,
+    /// `tokenmap` entries marked delisted after a CoinGecko 404
+    pub delisted_count: usize,
+}
+
+impl SyncStats {
+    /// Adds another run's counters to this one
+    pub fn merge(&mut self, other: SyncStats) {
+        self.inserted += other.inserted;
+        self.updated += other.updated;
+        self.pages += other.pages;
+        self.fetched += other.fetched;
+        self.deactivated += other.deactivated;
+        self.delisted_count += other.delisted_count;
+    }
+}
@@ -0,0 +1,129 @@
+//! Token risk scoring
+//!
+//! Combines several weak signals (verification status, explorer scam flag,
+//! missing metadata, market cap) into a 0-100 score and a coarse bucket
+//! that the UI can use to warn users proportionally.
+
+/// Points added when Blockscout flags the contract as a scam
+const SCAM_POINTS: i16 = 70;
+/// Points added when the contract source is not verified
+const UNVERIFIED_POINTS: i16 = 20;
+/// Points added when the token has no homepage, image or description
+const MISSING_METADATA_POINTS: i16 = 10;
+/// Points added when the market cap is below [`LOW_MARKET_CAP_USD`]
+const LOW_MARKET_CAP_POINTS: i16 = 15;
+/// Market cap (USD) below which a token counts as very small
+const LOW_MARKET_CAP_USD: f64 = 100_000.0;
+/// Maximum risk score
+const MAX_SCORE: i16 = 100;
+
+/// Signals available for scoring a token
+#[derive(Debug, Default, Clone, Copy)]
+pub struct RiskInputs {
+    /// Contract verification status (`None` if unknown)
+    pub is_verified: Option<bool>,
+    /// Blockscout scam flag
+    pub is_scam: bool,
+    /// Whether any of homepage/image/description is present
+    pub has_metadata: bool,
+    /// Market cap in USD from `marketdata` (`None` if not listed)
+    pub market_cap: Option<f64>,
+}
+
+/// Computes a risk score (0-100) and its bucket
+///
+/// # Buckets
+/// - `low`: 0-29
+/// - `medium`: 30-59
+/// - `high`: 60-100
+///
+/// # Returns
+/// `(score, risk_level)`; `risk_level` is `None` only when there is no
+/// signal at all to judge by (verification unknown, not flagged, no market data).
+pub fn compute_risk(inputs: RiskInputs) -> (i16, Option<String>) {
+    let mut score = 0;
+
+    if inputs.is_scam {
+        score += SCAM_POINTS;
+    }
+    if inputs.is_verified == Some(false) {
+        score += UNVERIFIED_POINTS;
+    }
+    if !inputs.has_metadata {
+        score += MISSING_METADATA_POINTS;
+    }
+    if inputs.market_cap.is_some_and(|cap| cap < LOW_MARKET_CAP_USD) {
+        score += LOW_MARKET_CAP_POINTS;
+    }
+    let score = score.min(MAX_SCORE);
+
+    let no_signal = inputs.is_verified.is_none() && !inputs.is_scam && inputs.market_cap.is_none();
+    if no_signal {
+        return (score, None);
+    }
+
+    let level = match score {
+        0..=29 => "low",
+        30..=59 => "medium",
+        _ => "high",
+    };
+    (score, Some(level.to_string()))
+}
+
+#[cfg(test)]
+mod tests {
+    use super::*;
+
+    #[test]
+    fn test_verified_listed_token_is_low() {
+        let inputs = RiskInputs {
+            is_verified: Some(true),
+            has_metadata: true,
+            market_cap: Some(5_000_000_000.0),
+            ..Default::default()
+        };
+        assert_eq!(compute_risk(inputs), (0, Some("low".to_string())));
+    }
+
+    #[test]
+    fn test_unverified_small_token_is_medium() {
+        let inputs = RiskInputs {
+            is_verified: Some(false),
+            has_metadata: false,
+            market_cap: Some(20_000.0),
+            ..Default::default()
+        };
+        assert_eq!(compute_risk(inputs), (45, Some("medium".to_string())));
+    }
+
+    #[test]
+    fn test_scam_flag_is_high() {
+        let inputs = RiskInputs {
+            is_verified: Some(true),
+            is_scam: true,
+            has_metadata: true,
+            ..Default::default()
+        };
+        assert_eq!(compute_risk(inputs), (70, Some("high".to_string())));
+    }
+
+    #[test]
+    fn test_score_is_capped() {
+        let inputs = RiskInputs {
+            is_verified: Some(false),
+            is_scam: true,
+            has_metadata: false,
+            market_cap: Some(1.0),
+        };
+        assert_eq!(compute_risk(inputs).0, MAX_SCORE);
+    }
+
+    #[test]
+    fn test_no_signal_has_no_level() {
+        let inputs = RiskInputs {
+            has_metadata: true,
+            ..Default::default()
+        };
+        assert_eq!(compute_risk(inputs), (0, None));
+    }
+}